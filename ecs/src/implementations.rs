@@ -13,29 +13,80 @@ impl<'w> Node<'w> for Entity {
         store.layout_type.get(self).cloned()
     }
 
+    fn layout_wrap(&self, store: &'_ Self::Data) -> Option<bool> {
+        store.layout_wrap.get(self).cloned()
+    }
+
+    fn layout_reverse(&self, store: &'_ Self::Data) -> Option<bool> {
+        store.layout_reverse.get(self).cloned()
+    }
+
+    fn align_baseline(&self, store: &'_ Self::Data) -> Option<bool> {
+        store.align_baseline.get(self).cloned()
+    }
+
+    fn layout_collapse(&self, store: &'_ Self::Data) -> Option<bool> {
+        store.layout_collapse.get(self).cloned()
+    }
+
+    fn collapse_priority(&self, store: &'_ Self::Data) -> Option<u32> {
+        store.collapse_priority.get(self).cloned()
+    }
+
+    fn layout_boundary(&self, store: &'_ Self::Data) -> Option<bool> {
+        store.layout_boundary.get(self).cloned()
+    }
+
     /// Get the  position type of the node
     fn position_type(&self, store: &'_ Self::Data) -> Option<PositionType> {
         store.position_type.get(self).cloned()
     }
 
     fn width(&self, store: &'_ Self::Data) -> Option<Units> {
+        store.width.get(self).map(|units| units.units())
+    }
+
+    fn width2(&self, store: &'_ Self::Data) -> Option<Units2> {
         store.width.get(self).cloned()
     }
 
     fn height(&self, store: &'_ Self::Data) -> Option<Units> {
+        store.height.get(self).map(|units| units.units())
+    }
+
+    fn height2(&self, store: &'_ Self::Data) -> Option<Units2> {
         store.height.get(self).cloned()
     }
 
     fn left(&self, store: &'_ Self::Data) -> Option<Units> {
+        store.left.get(self).map(|units| units.units())
+    }
+
+    fn left2(&self, store: &'_ Self::Data) -> Option<Units2> {
         store.left.get(self).cloned()
     }
+
     fn right(&self, store: &'_ Self::Data) -> Option<Units> {
+        store.right.get(self).map(|units| units.units())
+    }
+
+    fn right2(&self, store: &'_ Self::Data) -> Option<Units2> {
         store.right.get(self).cloned()
     }
+
     fn top(&self, store: &'_ Self::Data) -> Option<Units> {
+        store.top.get(self).map(|units| units.units())
+    }
+
+    fn top2(&self, store: &'_ Self::Data) -> Option<Units2> {
         store.top.get(self).cloned()
     }
+
     fn bottom(&self, store: &'_ Self::Data) -> Option<Units> {
+        store.bottom.get(self).map(|units| units.units())
+    }
+
+    fn bottom2(&self, store: &'_ Self::Data) -> Option<Units2> {
         store.bottom.get(self).cloned()
     }
 
@@ -87,6 +138,14 @@ impl<'w> Node<'w> for Entity {
         store.max_bottom.get(self).cloned()
     }
 
+    fn shrink(&self, store: &'_ Self::Data) -> Option<f32> {
+        store.shrink.get(self).cloned()
+    }
+
+    fn aspect_ratio(&self, store: &'_ Self::Data) -> Option<f32> {
+        store.aspect_ratio.get(self).cloned()
+    }
+
     fn min_width(&self, store: &'_ Self::Data) -> Option<Units> {
         store.min_width.get(self).cloned()
     }
@@ -103,6 +162,22 @@ impl<'w> Node<'w> for Entity {
         store.max_height.get(self).cloned()
     }
 
+    fn baseline(&self, store: &'_ Self::Data, _height: f32) -> Option<f32> {
+        store.baseline.get(self).cloned()
+    }
+
+    fn content_width(&self, store: &'_ Self::Data) -> Option<f32> {
+        store.content_width.get(self).cloned()
+    }
+
+    fn content_height(&self, store: &'_ Self::Data) -> Option<f32> {
+        store.content_height.get(self).cloned()
+    }
+
+    fn content_height_secondary(&self, store: &'_ Self::Data, width: f32) -> Option<f32> {
+        store.content_height_secondary.get(self).map(|measure| measure(width))
+    }
+
     fn row_between(&self, store: &'_ Self::Data) -> Option<Units> {
         store.row_between.get(self).cloned()
     }
@@ -111,19 +186,55 @@ impl<'w> Node<'w> for Entity {
         store.col_between.get(self).cloned()
     }
 
-    fn grid_rows(&self, store: &'_ Self::Data) -> Option<Vec<Units>> {
+    fn grid_rows(&self, store: &'_ Self::Data) -> Option<Vec<GridTrack>> {
         store.grid_rows.get(self).cloned()
     }
 
-    fn grid_cols(&self, store: &'_ Self::Data) -> Option<Vec<Units>> {
+    fn grid_cols(&self, store: &'_ Self::Data) -> Option<Vec<GridTrack>> {
         store.grid_cols.get(self).cloned()
     }
 
-    fn row_index(&self, store: &'_ Self::Data) -> Option<usize> {
+    fn grid_auto_flow(&self, store: &'_ Self::Data) -> Option<GridAutoFlow> {
+        store.grid_auto_flow.get(self).cloned()
+    }
+
+    fn grid_auto_rows(&self, store: &'_ Self::Data) -> Option<GridTrack> {
+        store.grid_auto_rows.get(self).cloned()
+    }
+
+    fn grid_auto_cols(&self, store: &'_ Self::Data) -> Option<GridTrack> {
+        store.grid_auto_cols.get(self).cloned()
+    }
+
+    fn grid_template_areas(&self, store: &'_ Self::Data) -> Option<Vec<Vec<String>>> {
+        store.grid_template_areas.get(self).cloned()
+    }
+
+    fn grid_row_lines(&self, store: &'_ Self::Data) -> Option<Vec<(String, usize)>> {
+        store.grid_row_lines.get(self).cloned()
+    }
+
+    fn grid_col_lines(&self, store: &'_ Self::Data) -> Option<Vec<(String, usize)>> {
+        store.grid_col_lines.get(self).cloned()
+    }
+
+    fn grid_area(&self, store: &'_ Self::Data) -> Option<String> {
+        store.grid_area.get(self).cloned()
+    }
+
+    fn row_lines(&self, store: &'_ Self::Data) -> Option<(String, String)> {
+        store.row_lines.get(self).cloned()
+    }
+
+    fn col_lines(&self, store: &'_ Self::Data) -> Option<(String, String)> {
+        store.col_lines.get(self).cloned()
+    }
+
+    fn row_index(&self, store: &'_ Self::Data) -> Option<isize> {
         store.row_index.get(self).cloned()
     }
 
-    fn col_index(&self, store: &'_ Self::Data) -> Option<usize> {
+    fn col_index(&self, store: &'_ Self::Data) -> Option<isize> {
         store.col_index.get(self).cloned()
     }
 
@@ -263,6 +374,11 @@ pub struct NodeCache {
     grid_row_max: HashMap<Entity, f32>,
     grid_col_max: HashMap<Entity, f32>,
 
+    grid_row_index: HashMap<Entity, usize>,
+    grid_col_index: HashMap<Entity, usize>,
+    grid_row_span: HashMap<Entity, usize>,
+    grid_col_span: HashMap<Entity, usize>,
+
     horizontal_free_space: HashMap<Entity, f32>,
     horizontal_stretch_sum: HashMap<Entity, f32>,
 
@@ -275,6 +391,11 @@ pub struct NodeCache {
     geometry_changed: HashMap<Entity, GeometryChanged>,
 
     visible: HashMap<Entity, bool>,
+    collapsed: HashMap<Entity, bool>,
+
+    measured_size: HashMap<(Entity, Direction, bool), MeasuredSize>,
+
+    scale_factor: Option<f32>,
 }
 
 impl NodeCache {
@@ -291,6 +412,11 @@ impl NodeCache {
         self.grid_row_max.insert(entity, Default::default());
         self.grid_col_max.insert(entity, Default::default());
 
+        self.grid_row_index.insert(entity, Default::default());
+        self.grid_col_index.insert(entity, Default::default());
+        self.grid_row_span.insert(entity, Default::default());
+        self.grid_col_span.insert(entity, Default::default());
+
         self.horizontal_free_space
             .insert(entity, Default::default());
         self.horizontal_stretch_sum
@@ -307,6 +433,7 @@ impl NodeCache {
         self.geometry_changed.insert(entity, Default::default());
 
         self.visible.insert(entity, true);
+        self.collapsed.insert(entity, false);
     }
 }
 
@@ -321,6 +448,22 @@ impl Cache for NodeCache {
         true
     }
 
+    fn collapsed(&self, node: Self::Item) -> bool {
+        if let Some(value) = self.collapsed.get(&node) {
+            return *value;
+        }
+
+        false
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor.unwrap_or(1.0)
+    }
+
+    fn measured_size(&self, node: Self::Item, axis: Direction, primary: bool) -> Option<MeasuredSize> {
+        self.measured_size.get(&(node, axis, primary)).cloned()
+    }
+
     fn geometry_changed(&self, node: Self::Item) -> GeometryChanged {
         if let Some(geometry_changed) = self.geometry_changed.get(&node) {
             return *geometry_changed;
@@ -444,11 +587,49 @@ impl Cache for NodeCache {
         *self.grid_col_max.get(&node).unwrap()
     }
 
+    fn grid_row_index(&self, node: Self::Item) -> usize {
+        *self.grid_row_index.get(&node).unwrap()
+    }
+
+    fn grid_col_index(&self, node: Self::Item) -> usize {
+        *self.grid_col_index.get(&node).unwrap()
+    }
+
+    fn grid_row_span(&self, node: Self::Item) -> usize {
+        *self.grid_row_span.get(&node).unwrap()
+    }
+
+    fn grid_col_span(&self, node: Self::Item) -> usize {
+        *self.grid_col_span.get(&node).unwrap()
+    }
+
     // Setters
     fn set_visible(&mut self, node: Self::Item, value: bool) {
         *self.visible.get_mut(&node).unwrap() = value;
     }
 
+    fn set_collapsed(&mut self, node: Self::Item, value: bool) {
+        *self.collapsed.get_mut(&node).unwrap() = value;
+    }
+
+    fn set_scale_factor(&mut self, value: f32) {
+        self.scale_factor = Some(value);
+    }
+
+    fn set_measured_size(
+        &mut self,
+        node: Self::Item,
+        axis: Direction,
+        primary: bool,
+        value: Option<MeasuredSize>,
+    ) {
+        if let Some(value) = value {
+            self.measured_size.insert((node, axis, primary), value);
+        } else {
+            self.measured_size.remove(&(node, axis, primary));
+        }
+    }
+
     fn set_child_width_sum(&mut self, node: Self::Item, value: f32) {
         *self.child_width_sum.get_mut(&node).unwrap() = value;
     }
@@ -496,7 +677,23 @@ impl Cache for NodeCache {
     }
 
     fn set_grid_col_max(&mut self, node: Self::Item, value: f32) {
-        *self.grid_row_max.get_mut(&node).unwrap() = value;
+        *self.grid_col_max.get_mut(&node).unwrap() = value;
+    }
+
+    fn set_grid_row_index(&mut self, node: Self::Item, value: usize) {
+        *self.grid_row_index.get_mut(&node).unwrap() = value;
+    }
+
+    fn set_grid_col_index(&mut self, node: Self::Item, value: usize) {
+        *self.grid_col_index.get_mut(&node).unwrap() = value;
+    }
+
+    fn set_grid_row_span(&mut self, node: Self::Item, value: usize) {
+        *self.grid_row_span.get_mut(&node).unwrap() = value;
+    }
+
+    fn set_grid_col_span(&mut self, node: Self::Item, value: usize) {
+        *self.grid_col_span.get_mut(&node).unwrap() = value;
     }
 
     fn set_width(&mut self, node: Self::Item, value: f32) {
@@ -1,5 +1,5 @@
-use morphorm::{LayoutType, PositionType, Units};
-use std::{collections::HashMap};
+use morphorm::{GridAutoFlow, GridTrack, LayoutType, PositionType, Units, Units2};
+use std::collections::HashMap;
 
 use crate::entity::Entity;
 
@@ -9,12 +9,18 @@ pub struct Store {
     pub visible: HashMap<Entity, bool>,
 
     pub layout_type: HashMap<Entity, LayoutType>,
+    pub layout_wrap: HashMap<Entity, bool>,
+    pub layout_reverse: HashMap<Entity, bool>,
+    pub align_baseline: HashMap<Entity, bool>,
+    pub layout_collapse: HashMap<Entity, bool>,
+    pub collapse_priority: HashMap<Entity, u32>,
+    pub layout_boundary: HashMap<Entity, bool>,
     pub position_type: HashMap<Entity, PositionType>,
 
-    pub left: HashMap<Entity, Units>,
-    pub right: HashMap<Entity, Units>,
-    pub top: HashMap<Entity, Units>,
-    pub bottom: HashMap<Entity, Units>,
+    pub left: HashMap<Entity, Units2>,
+    pub right: HashMap<Entity, Units2>,
+    pub top: HashMap<Entity, Units2>,
+    pub bottom: HashMap<Entity, Units2>,
 
     pub min_left: HashMap<Entity, Units>,
     pub max_left: HashMap<Entity, Units>,
@@ -25,12 +31,18 @@ pub struct Store {
     pub min_bottom: HashMap<Entity, Units>,
     pub max_bottom: HashMap<Entity, Units>,
 
-    pub width: HashMap<Entity, Units>,
-    pub height: HashMap<Entity, Units>,
+    pub width: HashMap<Entity, Units2>,
+    pub height: HashMap<Entity, Units2>,
     pub min_width: HashMap<Entity, Units>,
     pub max_width: HashMap<Entity, Units>,
     pub min_height: HashMap<Entity, Units>,
     pub max_height: HashMap<Entity, Units>,
+    pub aspect_ratio: HashMap<Entity, f32>,
+    pub shrink: HashMap<Entity, f32>,
+    pub baseline: HashMap<Entity, f32>,
+    pub content_width: HashMap<Entity, f32>,
+    pub content_height: HashMap<Entity, f32>,
+    pub content_height_secondary: HashMap<Entity, Box<dyn Fn(f32) -> f32>>,
 
     pub child_left: HashMap<Entity, Units>,
     pub child_right: HashMap<Entity, Units>,
@@ -39,13 +51,22 @@ pub struct Store {
     pub row_between: HashMap<Entity, Units>,
     pub col_between: HashMap<Entity, Units>,
 
-    pub grid_rows: HashMap<Entity, Vec<Units>>,
-    pub grid_cols: HashMap<Entity, Vec<Units>>,
+    pub grid_rows: HashMap<Entity, Vec<GridTrack>>,
+    pub grid_cols: HashMap<Entity, Vec<GridTrack>>,
+    pub grid_auto_flow: HashMap<Entity, GridAutoFlow>,
+    pub grid_auto_rows: HashMap<Entity, GridTrack>,
+    pub grid_auto_cols: HashMap<Entity, GridTrack>,
+    pub grid_template_areas: HashMap<Entity, Vec<Vec<String>>>,
+    pub grid_row_lines: HashMap<Entity, Vec<(String, usize)>>,
+    pub grid_col_lines: HashMap<Entity, Vec<(String, usize)>>,
 
-    pub row_index: HashMap<Entity, usize>,
-    pub col_index: HashMap<Entity, usize>,
+    pub row_index: HashMap<Entity, isize>,
+    pub col_index: HashMap<Entity, isize>,
     pub row_span: HashMap<Entity, usize>,
     pub col_span: HashMap<Entity, usize>,
+    pub grid_area: HashMap<Entity, String>,
+    pub row_lines: HashMap<Entity, (String, String)>,
+    pub col_lines: HashMap<Entity, (String, String)>,
 
     pub border: HashMap<Entity, Units>,
 
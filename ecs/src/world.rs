@@ -1,4 +1,4 @@
-use morphorm::{Units, LayoutType, PositionType};
+use morphorm::{Units, Units2, LayoutType, PositionType, GridAutoFlow, GridTrack};
 
 use crate::entity::{Entity, EntityManager};
 use crate::implementations::NodeCache;
@@ -33,44 +33,79 @@ impl World {
         entity
     }
 
+    /// Mark a node as needing layout after one of its properties has changed
+    pub fn mark_dirty(&mut self, entity: Entity) {
+        morphorm::mark_dirty(&mut self.cache, entity);
+    }
+
     /// Set the desired layout type
     pub fn set_layout_type(&mut self, entity: Entity, value: LayoutType) {
         self.store.layout_type.insert(entity, value);
     }
 
+    /// Set whether the children wrap onto new lines
+    pub fn set_layout_wrap(&mut self, entity: Entity, value: bool) {
+        self.store.layout_wrap.insert(entity, value);
+    }
+
+    /// Set whether the children are laid out in reverse order
+    pub fn set_layout_reverse(&mut self, entity: Entity, value: bool) {
+        self.store.layout_reverse.insert(entity, value);
+    }
+
+    /// Set whether the children are aligned on their baselines
+    pub fn set_align_baseline(&mut self, entity: Entity, value: bool) {
+        self.store.align_baseline.insert(entity, value);
+    }
+
+    /// Set whether the children collapse when they do not fit
+    pub fn set_layout_collapse(&mut self, entity: Entity, value: bool) {
+        self.store.layout_collapse.insert(entity, value);
+    }
+
+    /// Set the collapse priority
+    pub fn set_collapse_priority(&mut self, entity: Entity, value: u32) {
+        self.store.collapse_priority.insert(entity, value);
+    }
+
+    /// Set whether the node is a layout boundary
+    pub fn set_layout_boundary(&mut self, entity: Entity, value: bool) {
+        self.store.layout_boundary.insert(entity, value);
+    }
+
     /// Set the desired position type
     pub fn set_position_type(&mut self, entity: Entity, value: PositionType) {
         self.store.position_type.insert(entity, value);
     }
 
     /// Set the desired width
-    pub fn set_width(&mut self, entity: Entity, value: Units) {
-        self.store.width.insert(entity, value);
+    pub fn set_width<T: Into<Units2>>(&mut self, entity: Entity, value: T) {
+        self.store.width.insert(entity, value.into());
     }
 
     /// Set the desired height
-    pub fn set_height(&mut self, entity: Entity, value: Units) {
-        self.store.height.insert(entity, value);
+    pub fn set_height<T: Into<Units2>>(&mut self, entity: Entity, value: T) {
+        self.store.height.insert(entity, value.into());
     }
 
     /// Set the desired left space
-    pub fn set_left(&mut self, entity: Entity, value: Units) {
-        self.store.left.insert(entity, value);
+    pub fn set_left<T: Into<Units2>>(&mut self, entity: Entity, value: T) {
+        self.store.left.insert(entity, value.into());
     }
 
     /// Set the desired right space
-    pub fn set_right(&mut self, entity: Entity, value: Units) {
-        self.store.right.insert(entity, value);
+    pub fn set_right<T: Into<Units2>>(&mut self, entity: Entity, value: T) {
+        self.store.right.insert(entity, value.into());
     }
 
     /// Set the desired top space
-    pub fn set_top(&mut self, entity: Entity, value: Units) {
-        self.store.top.insert(entity, value);
+    pub fn set_top<T: Into<Units2>>(&mut self, entity: Entity, value: T) {
+        self.store.top.insert(entity, value.into());
     }
 
     /// Set the desired bottom space
-    pub fn set_bottom(&mut self, entity: Entity, value: Units) {
-        self.store.bottom.insert(entity, value);
+    pub fn set_bottom<T: Into<Units2>>(&mut self, entity: Entity, value: T) {
+        self.store.bottom.insert(entity, value.into());
     }
 
     /// Set the desired child_ space
@@ -112,28 +147,101 @@ impl World {
         self.store.col_between.insert(entity, value);
     }
 
+    /// Set the desired border width on all sides
+    pub fn set_border(&mut self, entity: Entity, value: Units) {
+        self.store.border.insert(entity, value);
+    }
+
     /// Set the desired grid rows
-    pub fn set_grid_rows(&mut self, entity: Entity, value: Vec<Units>) {
-        self.store.grid_rows.insert(entity, value);
+    pub fn set_grid_rows<T: Into<GridTrack>>(&mut self, entity: Entity, value: Vec<T>) {
+        self.store.grid_rows.insert(entity, value.into_iter().map(|track| track.into()).collect());
     }
 
     /// Set the desired grid columns
-    pub fn set_grid_cols(&mut self, entity: Entity, value: Vec<Units>) {
-        self.store.grid_cols.insert(entity, value);
+    pub fn set_grid_cols<T: Into<GridTrack>>(&mut self, entity: Entity, value: Vec<T>) {
+        self.store.grid_cols.insert(entity, value.into_iter().map(|track| track.into()).collect());
+    }
+
+    /// Set the desired grid auto flow
+    pub fn set_grid_auto_flow(&mut self, entity: Entity, value: GridAutoFlow) {
+        self.store.grid_auto_flow.insert(entity, value);
+    }
+
+    /// Set the desired size of implicit grid rows
+    pub fn set_grid_auto_rows<T: Into<GridTrack>>(&mut self, entity: Entity, value: T) {
+        self.store.grid_auto_rows.insert(entity, value.into());
+    }
+
+    /// Set the desired size of implicit grid columns
+    pub fn set_grid_auto_cols<T: Into<GridTrack>>(&mut self, entity: Entity, value: T) {
+        self.store.grid_auto_cols.insert(entity, value.into());
+    }
+
+    /// Set the named areas of the grid, as the name of each cell by row and then by column
+    pub fn set_grid_template_areas(&mut self, entity: Entity, value: Vec<Vec<&str>>) {
+        let areas = value.iter().map(|row| row.iter().map(|name| name.to_string()).collect()).collect();
+        self.store.grid_template_areas.insert(entity, areas);
+    }
+
+    /// Set the names of the lines between grid rows
+    pub fn set_grid_row_lines(&mut self, entity: Entity, value: Vec<(&str, usize)>) {
+        let lines = value.iter().map(|(name, line)| (name.to_string(), *line)).collect();
+        self.store.grid_row_lines.insert(entity, lines);
+    }
+
+    /// Set the names of the lines between grid columns
+    pub fn set_grid_col_lines(&mut self, entity: Entity, value: Vec<(&str, usize)>) {
+        let lines = value.iter().map(|(name, line)| (name.to_string(), *line)).collect();
+        self.store.grid_col_lines.insert(entity, lines);
+    }
+
+    /// Set the name of the grid area in which the node is placed
+    pub fn set_grid_area(&mut self, entity: Entity, name: &str) {
+        self.store.grid_area.insert(entity, name.to_string());
+    }
+
+    /// Set the names of the grid lines at which the rows of the node start and end
+    pub fn set_row_lines(&mut self, entity: Entity, start: &str, end: &str) {
+        self.store.row_lines.insert(entity, (start.to_string(), end.to_string()));
+    }
+
+    /// Set the names of the grid lines at which the columns of the node start and end
+    pub fn set_col_lines(&mut self, entity: Entity, start: &str, end: &str) {
+        self.store.col_lines.insert(entity, (start.to_string(), end.to_string()));
     }
 
     /// Set the desired grid row index
-    pub fn set_row(&mut self, entity: Entity, index: usize, span: usize) {
+    pub fn set_row(&mut self, entity: Entity, index: isize, span: usize) {
         self.store.row_index.insert(entity, index);
         self.store.row_span.insert(entity, span);
     }
 
     /// Set the desired grid row span
-    pub fn set_col(&mut self, entity: Entity, index: usize, span: usize) {
+    pub fn set_col(&mut self, entity: Entity, index: isize, span: usize) {
         self.store.col_index.insert(entity, index);
         self.store.col_span.insert(entity, span);
     }
 
+    /// Set the desired grid row span without a row index
+    pub fn set_row_span(&mut self, entity: Entity, span: usize) {
+        self.store.row_span.insert(entity, span);
+    }
+
+    /// Set the desired grid column span without a column index
+    pub fn set_col_span(&mut self, entity: Entity, span: usize) {
+        self.store.col_span.insert(entity, span);
+    }
+
+    /// Set the shrink factor
+    pub fn set_shrink(&mut self, entity: Entity, value: f32) {
+        self.store.shrink.insert(entity, value);
+    }
+
+    /// Set the desired ratio of the width to the height
+    pub fn set_aspect_ratio(&mut self, entity: Entity, value: f32) {
+        self.store.aspect_ratio.insert(entity, value);
+    }
+
     pub fn set_min_width(&mut self, entity: Entity, value: Units) {
         self.store.min_width.insert(entity, value);
     }
@@ -141,5 +249,33 @@ impl World {
     pub fn set_min_height(&mut self, entity: Entity, value: Units) {
         self.store.min_height.insert(entity, value);
     }
+
+    pub fn set_max_width(&mut self, entity: Entity, value: Units) {
+        self.store.max_width.insert(entity, value);
+    }
+
+    pub fn set_max_height(&mut self, entity: Entity, value: Units) {
+        self.store.max_height.insert(entity, value);
+    }
+
+    /// Set the distance from the top of the node to its baseline
+    pub fn set_baseline(&mut self, entity: Entity, value: f32) {
+        self.store.baseline.insert(entity, value);
+    }
+
+    /// Set the width of the content of the node
+    pub fn set_content_width(&mut self, entity: Entity, value: f32) {
+        self.store.content_width.insert(entity, value);
+    }
+
+    /// Set the height of the content of the node
+    pub fn set_content_height(&mut self, entity: Entity, value: f32) {
+        self.store.content_height.insert(entity, value);
+    }
+
+    /// Set the function which measures the height of the content of the node for a width, such as for wrapping text
+    pub fn set_content_height_secondary<F: Fn(f32) -> f32 + 'static>(&mut self, entity: Entity, measure: F) {
+        self.store.content_height_secondary.insert(entity, Box::new(measure));
+    }
     
 }
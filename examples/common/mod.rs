@@ -57,12 +57,25 @@ pub fn render(mut world: World, root: Entity) {
             Event::WindowEvent { ref event, .. } => match event {
                 WindowEvent::Resized(physical_size) => {
                     windowed_context.resize(*physical_size);
-                    world.set_width(root, Units::Pixels(physical_size.width as f32));
-                    world.set_height(root, Units::Pixels(physical_size.height as f32));
-                    world.cache.set_width(root, physical_size.width as f32);
-                    world.cache.set_height(root, physical_size.height as f32);
 
-                    layout(&mut world.cache, &world.tree, &world.store);
+                    // The tree is laid out in logical pixels which are snapped to the physical pixels of the window
+                    let scale_factor = window.scale_factor() as f32;
+                    let width = physical_size.width as f32 / scale_factor;
+                    let height = physical_size.height as f32 / scale_factor;
+                    world.set_width(root, Units::Pixels(width));
+                    world.set_height(root, Units::Pixels(height));
+
+                    let options =
+                        LayoutOptions::default().rounding(Rounding::Pixels).scale_factor(scale_factor);
+                    layout_subtree_with_options(
+                        &mut world.cache,
+                        &world.tree,
+                        &world.store,
+                        root,
+                        (0.0, 0.0),
+                        (width, height),
+                        &options,
+                    );
                 }
                 WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
 
@@ -97,10 +110,10 @@ pub fn render(mut world: World, root: Entity) {
                 );
 
                 world.tree.down_iter(|node| {
-                    let posx = world.cache.posx(node);
-                    let posy = world.cache.posy(node);
-                    let width = world.cache.width(node);
-                    let height = world.cache.height(node);
+                    let posx = world.cache.physical_posx(node);
+                    let posy = world.cache.physical_posy(node);
+                    let width = world.cache.physical_width(node);
+                    let height = world.cache.physical_height(node);
 
                     let red = world.store.red.get(&node).unwrap_or(&0u8);
                     let green = world.store.green.get(&node).unwrap_or(&0u8);
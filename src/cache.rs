@@ -1,4 +1,4 @@
-use crate::types::{Direction, GeometryChanged};
+use crate::types::{Direction, GeometryChanged, MeasuredSize};
 use crate::{LayoutType, Node};
 
 /// The Cache stores the result of layout as well as intermediate values for each node
@@ -13,18 +13,25 @@ pub trait Cache {
     /// Get the visibility flag of the node
     fn visible(&self, node: Self::Item) -> bool;
 
-    /// Get the computed width of a node
+    /// Get the collapsed flag of the node, which is set when the node is left out of layout because it does not fit
+    /// within its collapsing parent, while its visibility flag is left as it is
+    fn collapsed(&self, node: Self::Item) -> bool;
+
+    /// Get the computed width of a node in logical pixels
     fn width(&self, node: Self::Item) -> f32;
 
-    /// Get the computed height of a node
+    /// Get the computed height of a node in logical pixels
     fn height(&self, node: Self::Item) -> f32;
 
-    /// Get the computed x position of a node
+    /// Get the computed x position of a node in logical pixels
     fn posx(&self, node: Self::Item) -> f32;
 
-    /// Get the computed y position of a node
+    /// Get the computed y position of a node in logical pixels
     fn posy(&self, node: Self::Item) -> f32;
 
+    /// Get the scale factor from logical to physical pixels which the last layout was computed with
+    fn scale_factor(&self) -> f32;
+
     /// Get the computed space to the left of a node
     fn left(&self, node: Self::Item) -> f32;
     /// Get the computed space to the right of a node
@@ -49,22 +56,67 @@ pub trait Cache {
     /// Get the computed sum of the widths of the child nodes
     fn child_height_sum(&self, node: Self::Item) -> f32;
 
-    /// Get the computed maximum grid row
+    /// Get the computed total height of the grid rows
     fn grid_row_max(&self, node: Self::Item) -> f32;
 
-    /// Set the computed maximum grid row size
+    /// Set the computed total height of the grid rows
     fn set_grid_row_max(&mut self, node: Self::Item, value: f32);
 
-    /// Get the computed maximum grid column
+    /// Get the computed total width of the grid columns
     fn grid_col_max(&self, node: Self::Item) -> f32;
 
-    /// Set the computed maximum grid column size
+    /// Set the computed total width of the grid columns
     fn set_grid_col_max(&mut self, node: Self::Item, value: f32);
 
+    /// Get the computed grid row index of a node
+    fn grid_row_index(&self, node: Self::Item) -> usize;
+
+    /// Set the computed grid row index of a node
+    fn set_grid_row_index(&mut self, node: Self::Item, value: usize);
+
+    /// Get the computed grid column index of a node
+    fn grid_col_index(&self, node: Self::Item) -> usize;
+
+    /// Set the computed grid column index of a node
+    fn set_grid_col_index(&mut self, node: Self::Item, value: usize);
+
+    /// Get the computed number of grid rows spanned by a node
+    fn grid_row_span(&self, node: Self::Item) -> usize;
+
+    /// Set the computed number of grid rows spanned by a node
+    fn set_grid_row_span(&mut self, node: Self::Item, value: usize);
+
+    /// Get the computed number of grid columns spanned by a node
+    fn grid_col_span(&self, node: Self::Item) -> usize;
+
+    /// Set the computed number of grid columns spanned by a node
+    fn set_grid_col_span(&mut self, node: Self::Item, value: usize);
+
+    /// Get the content size of a node measured along the direction by the primary or secondary measurement
+    fn measured_size(
+        &self,
+        node: Self::Item,
+        axis: Direction,
+        primary: bool,
+    ) -> Option<MeasuredSize>;
+
+    /// Set the content size of a node measured along the direction, or clear it so that the node is measured again
+    fn set_measured_size(
+        &mut self,
+        node: Self::Item,
+        axis: Direction,
+        primary: bool,
+        value: Option<MeasuredSize>,
+    );
+
     // Setters
 
     fn set_visible(&mut self, node: Self::Item, value: bool);
 
+    fn set_collapsed(&mut self, node: Self::Item, value: bool);
+
+    fn set_scale_factor(&mut self, value: f32);
+
     fn set_geo_changed(&mut self, node: Self::Item, flag: GeometryChanged, value: bool);
 
     fn set_child_width_sum(&mut self, node: Self::Item, value: f32);
@@ -145,8 +197,20 @@ pub trait Cache {
     }
     fn grid_row_col_max(&self, node: Self::Item, axis: Direction) -> f32 {
         match axis {
-            Direction::X => self.grid_row_max(node),
-            Direction::Y => self.grid_col_max(node),
+            Direction::X => self.grid_col_max(node),
+            Direction::Y => self.grid_row_max(node),
+        }
+    }
+    fn grid_row_col_index(&self, node: Self::Item, axis: Direction) -> usize {
+        match axis {
+            Direction::X => self.grid_col_index(node),
+            Direction::Y => self.grid_row_index(node),
+        }
+    }
+    fn grid_row_col_span(&self, node: Self::Item, axis: Direction) -> usize {
+        match axis {
+            Direction::X => self.grid_col_span(node),
+            Direction::Y => self.grid_row_span(node),
         }
     }
     fn child_size_column(&self, node: Self::Item, axis: Direction) -> f32 {
@@ -166,6 +230,7 @@ pub trait Cache {
             LayoutType::Column => self.child_size_column(node, dir),
             LayoutType::Row => self.child_size_row(node, dir),
             LayoutType::Grid => self.grid_row_col_max(node, dir),
+            LayoutType::Overlay => self.child_size_max(node, dir),
         }
     }
     fn free_space(&self, node: Self::Item, dir: Direction) -> f32 {
@@ -181,6 +246,25 @@ pub trait Cache {
         }
     }
 
+    // physical getters
+
+    /// Get the computed width of a node in physical pixels
+    fn physical_width(&self, node: Self::Item) -> f32 {
+        self.width(node) * self.scale_factor()
+    }
+    /// Get the computed height of a node in physical pixels
+    fn physical_height(&self, node: Self::Item) -> f32 {
+        self.height(node) * self.scale_factor()
+    }
+    /// Get the computed x position of a node in physical pixels
+    fn physical_posx(&self, node: Self::Item) -> f32 {
+        self.posx(node) * self.scale_factor()
+    }
+    /// Get the computed y position of a node in physical pixels
+    fn physical_posy(&self, node: Self::Item) -> f32 {
+        self.posy(node) * self.scale_factor()
+    }
+
     // generic setters
     fn set_size(&mut self, node: Self::Item, value: f32, axis: Direction) {
         match axis {
@@ -226,8 +310,20 @@ pub trait Cache {
     }
     fn set_grid_row_col_max(&mut self, node: Self::Item, value: f32, axis: Direction) {
         match axis {
-            Direction::X => self.set_grid_row_max(node, value),
-            Direction::Y => self.set_grid_col_max(node, value),
+            Direction::X => self.set_grid_col_max(node, value),
+            Direction::Y => self.set_grid_row_max(node, value),
+        }
+    }
+    fn set_grid_row_col_index(&mut self, node: Self::Item, value: usize, axis: Direction) {
+        match axis {
+            Direction::X => self.set_grid_col_index(node, value),
+            Direction::Y => self.set_grid_row_index(node, value),
+        }
+    }
+    fn set_grid_row_col_span(&mut self, node: Self::Item, value: usize, axis: Direction) {
+        match axis {
+            Direction::X => self.set_grid_col_span(node, value),
+            Direction::Y => self.set_grid_row_span(node, value),
         }
     }
     fn set_free_space(&mut self, node: Self::Item, value: f32, dir: Direction) {
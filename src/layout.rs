@@ -1,7 +1,10 @@
 use crate::Cache;
 use crate::Hierarchy;
 use crate::Node;
-use crate::{Direction, GeometryChanged, LayoutType, PositionType, Units};
+use crate::{
+    Direction, GeometryChanged, GridTrack, LayoutOptions, LayoutType, MeasuredSize, PositionType,
+    Rounding, Units, Units2,
+};
 
 use smallvec::SmallVec;
 
@@ -12,6 +15,17 @@ enum Axis {
     After,
 }
 
+// How the size of a grid track is determined when fitting the tracks to the children placed in them
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TrackSizing {
+    // The track has a known size which is not affected by its children
+    Fixed,
+    // The track grows to fit its children
+    Measured,
+    // The track takes up a share of the free space
+    Flexible,
+}
+
 #[derive(Clone, Copy)]
 pub struct ComputedData<N: for<'w> Node<'w>> {
     node: N,
@@ -22,6 +36,16 @@ pub struct ComputedData<N: for<'w> Node<'w>> {
     axis: Axis,
 }
 
+// The space along one direction which the space and size of a node are incorporated into, where the fixed values are
+// taken from the free space and the stretch values are added to the stretch sum and buffered until it is shared out
+struct AxisSpace<'b, N: for<'w> Node<'w>> {
+    node: N,
+    parent_size: f32,
+    free_space: &'b mut f32,
+    stretch_sum: &'b mut f32,
+    buffer: &'b mut SmallVec<[ComputedData<N>; 3]>,
+}
+
 /// Perform a layout calculation on the visual tree of nodes, the resulting positions and sizes are stored within the provided cache
 pub fn layout<'a, C, H>(
     cache: &mut C,
@@ -31,60 +55,65 @@ pub fn layout<'a, C, H>(
     C: Cache<Item = <H as Hierarchy<'a>>::Item>,
     H: Hierarchy<'a>,
 {
+    layout_with_options(cache, hierarchy, store, &LayoutOptions::default());
+}
+
+/// Perform a layout calculation on the visual tree of nodes with the provided options, the resulting positions and
+/// sizes are stored within the provided cache
+///
+/// The options select the rounding of the results, the scale factor to physical pixels, and the size of nodes without a
+/// width or height, so that the same tree can be laid out with different policies without rebuilding the crate with
+/// different features. The sizes measured for a node are reused while it is not marked as dirty and the size it is
+/// measured against stays the same, so a node whose content or properties have changed since the last layout must be
+/// marked with [mark_dirty], and the same options must be used.
+pub fn layout_with_options<'a, C, H>(
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    cache.set_scale_factor(options.scale_factor);
+
+    // A dirty node marks its ancestors as dirty, as the sizes measured for them include the size of their subtree
+    hierarchy.up_iter(|node| {
+        if !cache.geometry_changed(node).intersects(GeometryChanged::CHANGE) {
+            return;
+        }
+
+        if let Some(parent) = hierarchy.parent(node) {
+            cache.set_geo_changed(
+                parent,
+                GeometryChanged::CHANGE_WIDTH | GeometryChanged::CHANGE_HEIGHT,
+                true,
+            );
+        }
+    });
+
     // Step 1 - Determine fist and last parent-directed child of each node and cache it
     // This needs to be done at least once before the rest of layout and when the position_type of a node changes
     hierarchy.down_iter(|parent| {
+        // Every node is recalculated, so none of them need to be marked as dirty anymore, while only the dirty nodes
+        // may have changed since they were measured
+        if cache.geometry_changed(parent).intersects(GeometryChanged::CHANGE) {
+            clear_measured_size(parent, cache);
+        }
+        cache.set_geo_changed(parent, GeometryChanged::CHANGE, false);
+
         // Skip non-visible nodes
-        let visible = cache.visible(parent);
+        let visible = is_shown(cache, parent);
         if !visible {
             return;
         }
 
-        // Reset the sum and max for the parent
-        cache.set_child_width_sum(parent, 0.0);
-        cache.set_child_height_sum(parent, 0.0);
-        cache.set_child_width_max(parent, 0.0);
-        cache.set_child_height_max(parent, 0.0);
-
         cache.set_geo_changed(parent, GeometryChanged::POSX_CHANGED, false);
         cache.set_geo_changed(parent, GeometryChanged::POSY_CHANGED, false);
         cache.set_geo_changed(parent, GeometryChanged::WIDTH_CHANGED, false);
         cache.set_geo_changed(parent, GeometryChanged::HEIGHT_CHANGED, false);
 
-        let mut found_first = false;
-        let mut last_child = None;
-
-        hierarchy.child_iter(parent, |node| {
-            // Skip non-visible nodes
-            let visible = cache.visible(node);
-            if !visible {
-                return;
-            }
-
-            cache.set_stack_first_child(node, false);
-            cache.set_stack_last_child(node, false);
-
-            let position_type = node.position_type(store).unwrap_or_default();
-
-            match position_type {
-                PositionType::ParentDirected => {
-                    if !found_first {
-                        found_first = true;
-                        cache.set_stack_first_child(node, true);
-                    }
-                    last_child = Some(node);
-                }
-
-                PositionType::SelfDirected => {
-                    cache.set_stack_first_child(node, true);
-                    cache.set_stack_last_child(node, true);
-                }
-            }
-        });
-
-        if let Some(last_child) = last_child {
-            cache.set_stack_last_child(last_child, true);
-        }
+        step1_parent(parent, cache, hierarchy, store);
     });
 
     // Step 2 - Iterate up the hierarchy
@@ -92,221 +121,1168 @@ pub fn layout<'a, C, H>(
     // to determine the width/height of parent nodes when set to Auto
     hierarchy.up_iter(|node| {
         // Skip non-visible nodes
-        let visible = cache.visible(node);
+        let visible = is_shown(cache, node);
         if !visible {
             return;
         }
 
-        let parent = hierarchy.parent(node);
-
-        step2(node, parent, cache, store, Direction::X);
-        step2(node, parent, cache, store, Direction::Y);
+        step2_node(node, hierarchy.parent(node), cache, hierarchy, store, options);
     });
 
     // Step 3 - Iterate down the hierarchy
     hierarchy.down_iter(|parent| {
-        let visible = cache.visible(parent);
+        let visible = is_shown(cache, parent);
         if !visible {
             return;
         }
 
-        let parent_layout_type = parent.layout_type(store).unwrap_or_default();
-
-        match parent_layout_type {
-            LayoutType::Row => {
-                step3_row_col(parent, cache, hierarchy, store, Direction::X, true);
-                step3_row_col(parent, cache, hierarchy, store, Direction::Y, false);
-            }
+        step3_parent(parent, cache, hierarchy, store, options);
+    });
+}
 
-            LayoutType::Column => {
-                step3_row_col(parent, cache, hierarchy, store, Direction::Y, true);
-                step3_row_col(parent, cache, hierarchy, store, Direction::X, false);
-            }
+/// Mark a node as needing layout, such as after one of its properties has changed, so that it is recalculated by the
+/// next call to [relayout] and measured again by the next call to [layout]
+pub fn mark_dirty<C: Cache>(cache: &mut C, node: C::Item) {
+    cache.set_geo_changed(node, GeometryChanged::CHANGE, true);
+}
 
-            LayoutType::Grid => step3_grid(parent, cache, hierarchy, store),
-        }
-    });
+/// Perform a layout calculation on only the nodes which have been marked as dirty, the resulting positions and sizes
+/// are stored within the provided cache
+pub fn relayout<'a, C, H>(
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    relayout_with_options(cache, hierarchy, store, &LayoutOptions::default());
 }
 
-pub fn step2<'a, C, N>(
-    node: N,
-    parent: Option<N>,
+/// Perform a layout calculation on only the nodes which have been marked as dirty with the provided options, the
+/// resulting positions and sizes are stored within the provided cache
+///
+/// A dirty node marks its parent as dirty, and so on up the hierarchy until a layout boundary is reached, since a change
+/// within a node whose size does not depend on its children cannot affect the nodes around it. The dirty nodes and all
+/// of their descendants are then recalculated, while the rest of the cache is left as it was. The content of a clean
+/// node is only measured again when the size it is measured against has changed. The hierarchy must have been laid
+/// out with [layout] or [layout_with_options] before, using the same options.
+pub fn relayout_with_options<'a, C, H>(
     cache: &mut C,
-    store: &'a N::Data,
-    dir: Direction,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
 ) where
-    C: Cache<Item = N>,
-    N: Node<'a>,
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
 {
-    let parent_layout_type =
-        parent.map_or(None, |parent| parent.layout_type(store)).unwrap_or_default();
-    let layout_type = node.layout_type(store).unwrap_or_default();
+    cache.set_scale_factor(options.scale_factor);
 
-    let child_before =
-        parent.map_or(None, |parent| parent.child_before(store, dir)).unwrap_or_default();
-    let child_after =
-        parent.map_or(None, |parent| parent.child_after(store, dir)).unwrap_or_default();
-    let row_col_between =
-        parent.map_or(None, |parent| parent.row_col_between(store, dir)).unwrap_or_default();
-    let mut before = node.before(store, dir).unwrap_or_default();
-    let mut after = node.after(store, dir).unwrap_or_default();
-    let min_before = node.min_before(store, dir).unwrap_or_default().value_or(0.0, -f32::MAX);
-    let max_before = node.max_before(store, dir).unwrap_or_default().value_or(f32::MAX, f32::MAX);
-    let min_after = node.min_after(store, dir).unwrap_or_default().value_or(0.0, -f32::MAX);
-    let max_after = node.max_after(store, dir).unwrap_or_default().value_or(f32::MAX, f32::MAX);
-    let size = node.size(store, dir).unwrap_or(Units::Stretch(1.0));
+    // Mark the ancestors of dirty nodes as dirty, up to the first layout boundary
+    hierarchy.up_iter(|node| {
+        let flags = cache.geometry_changed(node);
+        if !flags.intersects(GeometryChanged::CHANGE) {
+            return;
+        }
 
-    // integrate content_width and content_height into the child max and sum
-    // this means that if a node has both content and children (weird!) they should overlap
-    let content_size = node.content_size(store, dir).unwrap_or_default();
-    cache.set_child_size_max(node, cache.child_size_max(node, dir).max(content_size), dir);
-    cache.set_child_size_sum(node, cache.child_size_sum(node, dir).max(content_size), dir);
+        // A node which was marked itself may take up different space in its parent, even when its size is fixed
+        let marked = flags.intersects(GeometryChanged::CHANGE_POSX | GeometryChanged::CHANGE_POSY);
+        if marked || !is_layout_boundary(node, store, options) {
+            if let Some(parent) = hierarchy.parent(node) {
+                cache.set_geo_changed(
+                    parent,
+                    GeometryChanged::CHANGE_WIDTH | GeometryChanged::CHANGE_HEIGHT,
+                    true,
+                );
+            }
+        }
+    });
 
-    // If Auto, then set the minimum height to be at least the height_sum/height_max/col_max of the children (depending on layout type)
-    let mut min_size = node
-        .min_size(store, dir)
-        .unwrap_or_default()
-        .value_or(0.0, cache.child_size_layout(node, dir, layout_type));
-    min_size = min_size.clamp(0.0, f32::MAX);
+    // The descendants of a dirty node are recalculated along with it, as step 2 of a node such as a grid or a wrapping
+    // stack reads the positions and sizes of its children, which have since been replaced in step 3
+    hierarchy.down_iter(|parent| {
+        if !cache.geometry_changed(parent).intersects(GeometryChanged::CHANGE) {
+            return;
+        }
 
-    let mut max_size = node.max_size(store, dir).unwrap_or_default().value_or(f32::MAX, f32::MAX);
-    max_size = max_size.max(min_size);
+        hierarchy.child_iter(parent, |node| {
+            cache.set_geo_changed(
+                node,
+                GeometryChanged::CHANGE_POSX | GeometryChanged::CHANGE_POSY,
+                true,
+            );
+        });
+    });
 
-    let border_before = node.border_before(store, dir).unwrap_or_default().value_or(0.0, 0.0);
-    let border_after = node.border_after(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+    // Step 1 - Reset the dirty nodes
+    // The sizes measured for a node which was marked, or which contains a marked node, are cleared as they may depend
+    // on the properties which changed, while the descendants which are only moved keep them for as long as they are
+    // measured under the same constraints
+    hierarchy.down_iter(|parent| {
+        let flags = cache.geometry_changed(parent);
+        if flags.intersects(GeometryChanged::CHANGE_WIDTH | GeometryChanged::CHANGE_HEIGHT) {
+            clear_measured_size(parent, cache);
+        }
 
-    // If left/right/top/bottom are Auto then the parent child_left/child_right/child_top/child_bottom overrides them
-    // The override is also dependent on position in stack (first, last, other) and layout type
-    if let Some(layout_dir) = parent_layout_type.direction() {
-        if layout_dir == dir {
-            if before == Units::Auto {
-                if cache.stack_first_child(node) {
-                    before = child_before;
-                } else {
-                    before = row_col_between;
-                }
-            }
-            if after == Units::Auto {
-                if cache.stack_last_child(node) {
-                    after = child_after;
-                }
-            }
-        } else {
-            if before == Units::Auto {
-                before = child_before;
-            }
-            if after == Units::Auto {
-                after = child_after;
-            }
+        let dirty = flags.intersects(GeometryChanged::CHANGE);
+
+        let visible = is_shown(cache, parent);
+        if !visible {
+            return;
         }
-    }
 
-    let mut new_before = 0.0;
-    let mut new_size = 0.0;
-    let mut new_after = 0.0;
-    let mut used_space = 0.0;
+        cache.set_geo_changed(parent, GeometryChanged::POSX_CHANGED, false);
+        cache.set_geo_changed(parent, GeometryChanged::POSY_CHANGED, false);
+        cache.set_geo_changed(parent, GeometryChanged::WIDTH_CHANGED, false);
+        cache.set_geo_changed(parent, GeometryChanged::HEIGHT_CHANGED, false);
 
-    match parent_layout_type {
-        LayoutType::Column | LayoutType::Row => {
-            match before {
-                Units::Pixels(val) => {
-                    new_before = val.clamp(min_before, max_before);
-                    used_space += new_before;
-                }
+        if dirty {
+            step1_parent(parent, cache, hierarchy, store);
+        }
+    });
 
-                Units::Stretch(_) => {
-                    used_space += min_before.clamp(0.0, f32::MAX);
-                }
+    // Step 2 - Iterate up the hierarchy
+    // The sums of the dirty nodes were reset, so all of their children are accumulated again, while the
+    // children of clean nodes keep the sizes from the previous layout. This stops at the layout boundaries, which
+    // keep the size they were given by their parent.
+    hierarchy.up_iter(|node| {
+        let visible = is_shown(cache, node);
+        if !visible {
+            return;
+        }
 
-                _ => {}
-            }
+        let dirty = hierarchy.parent(node).unwrap_or(node);
+        if cache.geometry_changed(dirty).intersects(GeometryChanged::CHANGE) {
+            step2_node(node, hierarchy.parent(node), cache, hierarchy, store, options);
+        }
+    });
 
-            match size {
-                Units::Pixels(val) => {
-                    new_size = val.clamp(min_size, max_size);
-                    used_space += new_size;
-                }
+    // Step 3 - Iterate down the hierarchy
+    hierarchy.down_iter(|parent| {
+        if !cache.geometry_changed(parent).intersects(GeometryChanged::CHANGE) {
+            return;
+        }
+
+        cache.set_geo_changed(parent, GeometryChanged::CHANGE, false);
+
+        let visible = is_shown(cache, parent);
+        if !visible {
+            return;
+        }
 
-                Units::Auto => {
-                    new_size = cache.child_size_layout(node, dir, layout_type);
+        step3_parent(parent, cache, hierarchy, store, options);
+    });
+}
 
-                    new_size = new_size.clamp(min_size, max_size);
+/// Perform a layout calculation on the subtree under a node, which is placed at the position and laid out within the
+/// available size, the resulting positions and sizes are stored within the provided cache
+pub fn layout_subtree<'a, C, H>(
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    root: <H as Hierarchy<'a>>::Item,
+    position: (f32, f32),
+    available_size: (f32, f32),
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    layout_subtree_with_options(
+        cache,
+        hierarchy,
+        store,
+        root,
+        position,
+        available_size,
+        &LayoutOptions::default(),
+    );
+}
 
-                    new_size += border_before + border_after;
+/// Perform a layout calculation on the subtree under a node with the provided options, the resulting positions and
+/// sizes are stored within the provided cache
+///
+/// The node is sized as if it were the only child of a parent with the available size, ignoring its own space, and
+/// its actual parent is neither read nor written. Only the node and its descendants are changed in the cache, so a
+/// popup or an offscreen node can be laid out without disturbing the rest of the tree.
+pub fn layout_subtree_with_options<'a, C, H>(
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    root: <H as Hierarchy<'a>>::Item,
+    position: (f32, f32),
+    available_size: (f32, f32),
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let visible = is_shown(cache, root);
+    if !visible {
+        return;
+    }
 
-                    used_space += new_size;
-                }
+    cache.set_scale_factor(options.scale_factor);
 
-                Units::Stretch(_) => {
-                    used_space += min_size;
-                }
+    // Step 1 - Determine fist and last parent-directed child of each node in the subtree
+    down_iter_subtree(hierarchy, root, &mut |parent| {
+        cache.set_geo_changed(parent, GeometryChanged::CHANGE, false);
+        clear_measured_size(parent, cache);
 
-                _ => {}
-            }
+        let visible = is_shown(cache, parent);
+        if !visible {
+            return;
+        }
 
-            match after {
-                Units::Pixels(val) => {
-                    new_after = val.clamp(min_after, max_after);
-                    used_space += new_after;
-                }
+        cache.set_geo_changed(parent, GeometryChanged::POSX_CHANGED, false);
+        cache.set_geo_changed(parent, GeometryChanged::POSY_CHANGED, false);
+        cache.set_geo_changed(parent, GeometryChanged::WIDTH_CHANGED, false);
+        cache.set_geo_changed(parent, GeometryChanged::HEIGHT_CHANGED, false);
 
-                Units::Stretch(_) => {
-                    used_space += min_after.clamp(0.0, f32::MAX);
-                }
+        step1_parent(parent, cache, hierarchy, store);
+    });
 
-                _ => {}
+    // Step 2 - Iterate up the subtree
+    // The root is measured without its parent so that the sums of the parent are left as they are
+    hierarchy.child_iter(root, |child| {
+        up_iter_subtree(hierarchy, child, &mut |node| {
+            let visible = is_shown(cache, node);
+            if !visible {
+                return;
             }
 
-            let position_type = node.position_type(store).unwrap_or_default();
+            step2_node(node, hierarchy.parent(node), cache, hierarchy, store, options);
+        });
+    });
 
-            cache.set_new_size(node, new_size, dir);
-            cache.set_before(node, new_before, dir);
-            cache.set_after(node, new_after, dir);
+    step2_node(root, None, cache, hierarchy, store, options);
 
-            if let Some(parent) = parent {
-                if position_type == PositionType::ParentDirected {
-                    cache.set_child_size_sum(
-                        parent,
-                        cache.child_size_sum(parent, dir) + used_space,
-                        dir,
-                    );
+    let (posx, posy) = position;
+    let (available_width, available_height) = available_size;
+    let width = subtree_size(root, cache, store, Direction::X, available_width, options);
+    let height = subtree_size(root, cache, store, Direction::Y, available_height, options);
+    set_cell_layout(root, cache, Direction::X, posx, width, options);
+    set_cell_layout(root, cache, Direction::Y, posy, height, options);
 
-                    cache.set_child_size_max(
-                        parent,
-                        used_space.max(cache.child_size_max(parent, dir)),
-                        dir,
-                    );
-                }
-            }
+    // Step 3 - Iterate down the subtree
+    down_iter_subtree(hierarchy, root, &mut |parent| {
+        let visible = is_shown(cache, parent);
+        if !visible {
+            return;
         }
 
-        LayoutType::Grid => {
-            // TODO
+        step3_parent(parent, cache, hierarchy, store, options);
+    });
+}
+
+// Calls the function on a node and then its descendants, depth first with pre-order
+fn down_iter_subtree<'a, H, F>(hierarchy: &'a H, node: <H as Hierarchy<'a>>::Item, f: &mut F)
+where
+    H: Hierarchy<'a>,
+    F: FnMut(<H as Hierarchy<'a>>::Item),
+{
+    f(node);
+    hierarchy.child_iter(node, |child| down_iter_subtree(hierarchy, child, f));
+}
+
+// Calls the function on the descendants of a node and then the node itself, so that children come before their parent
+fn up_iter_subtree<'a, H, F>(hierarchy: &'a H, node: <H as Hierarchy<'a>>::Item, f: &mut F)
+where
+    H: Hierarchy<'a>,
+    F: FnMut(<H as Hierarchy<'a>>::Item),
+{
+    hierarchy.child_iter(node, |child| up_iter_subtree(hierarchy, child, f));
+    f(node);
+}
+
+// Resolves the size of the root of a subtree against the available size, from the size measured in step 2
+fn subtree_size<'a, C, N>(
+    root: N,
+    cache: &C,
+    store: &'a N::Data,
+    dir: Direction,
+    available: f32,
+    options: &LayoutOptions,
+) -> f32
+where
+    C: Cache<Item = N>,
+    N: Node<'a>,
+{
+    let size = cache.new_size(root, dir);
+
+    match root.size(store, dir).unwrap_or(options.default_size) {
+        Units::Percentage(_) | Units::Calc(_) => {
+            fixed_size(root, store, dir, Some(available), options).unwrap_or(size)
+        }
+
+        // A stretch size takes up the available size, within the min size measured in step 2
+        Units::Stretch(_) => {
+            let max_size =
+                root.max_size(store, dir).unwrap_or_default().value_or(available, f32::MAX);
+            options.round(available.min(max_size).max(size))
         }
+
+        _ => size,
     }
 }
 
-pub fn step3_row_col<'a, C, H>(
+// Returns true if a node is marked as a layout boundary, or its size is either fixed or resolved from its parent and
+// does not grow or shrink with its children, so that a change within the node cannot affect the layout of its ancestors
+fn is_layout_boundary<'a, N: Node<'a>>(
+    node: N,
+    store: &'a N::Data,
+    options: &LayoutOptions,
+) -> bool {
+    if node.layout_boundary(store).unwrap_or_default() {
+        return true;
+    }
+
+    let is_fixed = |units: Units| units.is_pixels() || units.is_percentage() || units.is_calc();
+
+    [Direction::X, Direction::Y].iter().all(|&dir| {
+        !node.size(store, dir).unwrap_or(options.default_size).is_auto()
+            && is_fixed(node.min_size(store, dir).unwrap_or_default())
+            && is_fixed(node.max_size(store, dir).unwrap_or(Units::Pixels(f32::MAX)))
+    })
+}
+
+// Returns true if a node takes part in layout, which is when it is visible and has not been collapsed by its parent
+fn is_shown<C: Cache>(cache: &C, node: C::Item) -> bool {
+    cache.visible(node) && !cache.collapsed(node)
+}
+
+// Resets the sums of a node and determines its first and last parent-directed child before its children are measured
+fn step1_parent<'a, C, H>(
     parent: <H as Hierarchy<'a>>::Item,
     cache: &mut C,
     hierarchy: &'a H,
     store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
-    dir: Direction,
-    primary: bool,
 ) where
     C: Cache<Item = <H as Hierarchy<'a>>::Item>,
     H: Hierarchy<'a>,
 {
-    let parent_layout_type = parent.layout_type(store).unwrap_or_default();
-    let child_before = parent.child_before(store, dir).unwrap_or_default();
-    let child_after = parent.child_after(store, dir).unwrap_or_default();
+    // Reset the sum and max for the parent
+    cache.set_child_width_sum(parent, 0.0);
+    cache.set_child_height_sum(parent, 0.0);
+    cache.set_child_width_max(parent, 0.0);
+    cache.set_child_height_max(parent, 0.0);
 
-    let row_col_between = parent.row_col_between(store, dir).unwrap_or_default();
+    // Children collapsed by the previous layout are shown again until they are found not to fit
+    hierarchy.child_iter(parent, |node| cache.set_collapsed(node, false));
 
-    let mut parent_size = cache.new_size(parent, dir);
-    let parent_width_hard = cache.new_width(parent);
+    set_stack_first_last(parent, cache, hierarchy, store);
 
-    let parent_border_before =
+    if parent.layout_type(store).unwrap_or_default() == LayoutType::Grid {
+        step1_grid(parent, cache, hierarchy, store);
+    }
+}
+
+// Measures a node from its children and adds it to the sums of its parent
+fn step2_node<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    parent: Option<<H as Hierarchy<'a>>::Item>,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    match node.layout_type(store).unwrap_or_default() {
+        // The auto size of a grid depends on its tracks, which are sized by the children placed in them
+        LayoutType::Grid => {
+            step2_grid(node, cache, hierarchy, store, Direction::X);
+            step2_grid(node, cache, hierarchy, store, Direction::Y);
+        }
+
+        // The auto size of a wrapping stack depends on the lines its children are split into
+        _ if node.layout_wrap(store).unwrap_or_default() => {
+            step2_wrap(node, cache, hierarchy, store, options);
+        }
+
+        // The auto height of a row depends on the space above and below the baselines of its children
+        LayoutType::Row if node.align_baseline(store).unwrap_or_default() => {
+            step2_baseline(node, cache, hierarchy, store);
+        }
+
+        _ => {}
+    }
+
+    for &dir in [Direction::X, Direction::Y].iter() {
+        if dir == Direction::Y {
+            step2_aspect_ratio(node, cache, hierarchy, store, options);
+        }
+
+        let shrunk_size = shrunk_content_size(node, cache, hierarchy, store, dir);
+        step2(node, parent, cache, store, dir, shrunk_size, options);
+    }
+}
+
+// Derives the heights of the children with an aspect ratio and a stretch width across a column or overlay, once the
+// width of the node is known, and grows the sums of the node by the height they gain
+fn step2_aspect_ratio<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    // The stretch width of a child in a row depends on its siblings, so it is only known in step 3
+    let layout_type = node.layout_type(store).unwrap_or_default();
+    if !matches!(layout_type, LayoutType::Column | LayoutType::Overlay)
+        || node.layout_wrap(store).unwrap_or_default()
+    {
+        return;
+    }
+
+    let width = node.size(store, Direction::X).unwrap_or(options.default_size);
+    if !width.is_pixels() && !width.is_auto() {
+        return;
+    }
+
+    let (_, content_width) = content_box(node, cache, store, Direction::X);
+
+    hierarchy.child_iter(node, |child| {
+        if !is_shown(cache, child)
+            || child.position_type(store).unwrap_or_default() == PositionType::SelfDirected
+            || !child.size(store, Direction::X).unwrap_or(options.default_size).is_stretch()
+        {
+            return;
+        }
+
+        let min_width = cache.new_size(child, Direction::X);
+        let max_width = child
+            .max_size(store, Direction::X)
+            .unwrap_or_default()
+            .value_or(content_width, f32::MAX)
+            .max(min_width);
+        let child_width =
+            (content_width - cache.before(child, Direction::X) - cache.after(child, Direction::X))
+                .clamp(min_width, max_width);
+
+        if let Some(height) =
+            aspect_ratio_size(child, store, Direction::Y, Some(child_width), options)
+        {
+            let max_height = child
+                .max_size(store, Direction::Y)
+                .unwrap_or_default()
+                .value_or(f32::MAX, f32::MAX);
+            let old_height = cache.new_size(child, Direction::Y);
+            let height = height.min(max_height).max(old_height);
+
+            cache.set_new_size(child, height, Direction::Y);
+            cache.set_child_size_sum(
+                node,
+                cache.child_size_sum(node, Direction::Y) + height - old_height,
+                Direction::Y,
+            );
+            cache.set_child_size_max(
+                node,
+                cache.child_size_max(node, Direction::Y).max(
+                    cache.before(child, Direction::Y) + height + cache.after(child, Direction::Y),
+                ),
+                Direction::Y,
+            );
+        }
+    });
+}
+
+// Positions and sizes the children of a node
+fn step3_parent<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let parent_layout_type = parent.layout_type(store).unwrap_or_default();
+    let parent_layout_wrap = parent.layout_wrap(store).unwrap_or_default();
+
+    match parent_layout_type {
+        LayoutType::Row if parent_layout_wrap => {
+            step3_wrap(parent, cache, hierarchy, store, Direction::X, options);
+        }
+
+        LayoutType::Column if parent_layout_wrap => {
+            step3_wrap(parent, cache, hierarchy, store, Direction::Y, options);
+        }
+
+        LayoutType::Row => {
+            step3_row_col(parent, cache, hierarchy, store, Direction::X, true, options);
+
+            if parent.layout_collapse(store).unwrap_or_default()
+                && step3_collapse(parent, cache, hierarchy, store, Direction::X)
+            {
+                step3_row_col(parent, cache, hierarchy, store, Direction::X, true, options);
+            }
+
+            step3_row_col(parent, cache, hierarchy, store, Direction::Y, false, options);
+
+            if parent.align_baseline(store).unwrap_or_default() {
+                step3_baseline(parent, cache, hierarchy, store, options);
+            }
+        }
+
+        LayoutType::Column => {
+            step3_row_col(parent, cache, hierarchy, store, Direction::Y, true, options);
+
+            if parent.layout_collapse(store).unwrap_or_default()
+                && step3_collapse(parent, cache, hierarchy, store, Direction::Y)
+            {
+                step3_row_col(parent, cache, hierarchy, store, Direction::Y, true, options);
+            }
+
+            step3_row_col(parent, cache, hierarchy, store, Direction::X, false, options);
+        }
+
+        LayoutType::Grid => step3_grid(parent, cache, hierarchy, store, options),
+
+        LayoutType::Overlay => step3_overlay(parent, cache, hierarchy, store, options),
+    }
+}
+
+// Determines the first and last parent-directed child of a node and stores them in the cache
+fn set_stack_first_last<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let mut first_child = None;
+    let mut last_child = None;
+
+    hierarchy.child_iter(parent, |node| {
+        // Skip non-visible nodes
+        let visible = is_shown(cache, node);
+        if !visible {
+            return;
+        }
+
+        cache.set_stack_first_child(node, false);
+        cache.set_stack_last_child(node, false);
+
+        let position_type = node.position_type(store).unwrap_or_default();
+
+        match position_type {
+            PositionType::ParentDirected => {
+                if first_child.is_none() {
+                    first_child = Some(node);
+                }
+                last_child = Some(node);
+            }
+
+            PositionType::SelfDirected => {
+                cache.set_stack_first_child(node, true);
+                cache.set_stack_last_child(node, true);
+            }
+        }
+    });
+
+    // The first child of a reversed stack is at the end of the stack
+    if parent.layout_reverse(store).unwrap_or_default() {
+        std::mem::swap(&mut first_child, &mut last_child);
+    }
+
+    if let Some(first_child) = first_child {
+        cache.set_stack_first_child(first_child, true);
+    }
+
+    if let Some(last_child) = last_child {
+        cache.set_stack_last_child(last_child, true);
+    }
+}
+
+/// Determine the row and column index and span of each child of a grid node and store them in the cache
+///
+/// Children with an explicit row and column index, or which name an area or lines of the grid, are placed first.
+/// If the grid has an auto flow then the remaining children are placed in order into the next free cell which fits
+/// their span, moving along the rows or columns of the grid depending on the flow. A dense flow searches from the
+/// start of the grid for each child instead.
+pub fn step1_grid<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let auto_flow = parent.grid_auto_flow(store);
+
+    // Cells are filled along the minor direction, with the major direction moving on to the next row or column
+    let (minor_dir, dense) = match auto_flow {
+        Some(auto_flow) => (auto_flow.direction(), auto_flow.is_dense()),
+        None => (Direction::X, false),
+    };
+    let major_dir = !minor_dir;
+
+    let major_count = parent.grid_rows_cols(store, major_dir).unwrap_or_default().len();
+    let minor_count = parent.grid_rows_cols(store, minor_dir).unwrap_or_default().len();
+
+    let mut occupied = GridOccupancy::default();
+    let mut auto_placed = Vec::new();
+
+    hierarchy.child_iter(parent, |node| {
+        let visible = is_shown(cache, node);
+        if !visible {
+            return;
+        }
+
+        let (major, major_span) = grid_placement(parent, node, store, major_dir, major_count);
+        let (minor, minor_span) = grid_placement(parent, node, store, minor_dir, minor_count);
+
+        cache.set_grid_row_col_span(node, major_span, major_dir);
+        cache.set_grid_row_col_span(node, minor_span, minor_dir);
+
+        let position_type = node.position_type(store).unwrap_or_default();
+
+        if auto_flow.is_some()
+            && position_type == PositionType::ParentDirected
+            && (major.is_none() || minor.is_none())
+        {
+            auto_placed.push((node, major, minor));
+            return;
+        }
+
+        let major = major.unwrap_or_default();
+        let minor = minor.unwrap_or_default();
+
+        cache.set_grid_row_col_index(node, major, major_dir);
+        cache.set_grid_row_col_index(node, minor, minor_dir);
+
+        if position_type == PositionType::ParentDirected {
+            occupied.insert(major, minor, major_span, minor_span);
+        }
+    });
+
+    let mut cursor = (0, 0);
+
+    for (node, major, minor) in auto_placed {
+        let major_span = cache.grid_row_col_span(node, major_dir);
+        let minor_span = cache.grid_row_col_span(node, minor_dir);
+
+        // Children which span more tracks than the grid has are placed at the start of a row or column
+        let last_minor = minor_count.saturating_sub(minor_span);
+
+        let start = if dense { (0, 0) } else { cursor };
+
+        let (major, minor) = match (major, minor) {
+            (Some(major), None) => {
+                let minor = (0..)
+                    .find(|&minor| occupied.is_free(major, minor, major_span, minor_span))
+                    .unwrap_or_default();
+                (major, minor)
+            }
+
+            (None, Some(minor)) => {
+                let major = (start.0..)
+                    .find(|&major| occupied.is_free(major, minor, major_span, minor_span))
+                    .unwrap_or_default();
+                (major, minor)
+            }
+
+            _ => {
+                let cell = (start.0..)
+                    .flat_map(|major| {
+                        let first_minor = if major == start.0 { start.1 } else { 0 };
+                        (first_minor..=last_minor).map(move |minor| (major, minor))
+                    })
+                    .find(|&(major, minor)| occupied.is_free(major, minor, major_span, minor_span))
+                    .unwrap_or_default();
+
+                cursor = (cell.0, cell.1 + minor_span);
+
+                cell
+            }
+        };
+
+        // A grid which is full up to its largest number of tracks places the remaining children in its last tracks
+        let major = major.min(max_grid_tracks(major_count) - major_span);
+        let minor = minor.min(max_grid_tracks(minor_count) - minor_span);
+
+        occupied.insert(major, minor, major_span, minor_span);
+
+        cache.set_grid_row_col_index(node, major, major_dir);
+        cache.set_grid_row_col_index(node, minor, minor_dir);
+    }
+}
+
+pub fn step2<'a, C, N>(
+    node: N,
+    parent: Option<N>,
+    cache: &mut C,
+    store: &'a N::Data,
+    dir: Direction,
+    shrunk_size: Option<f32>,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = N>,
+    N: Node<'a>,
+{
+    let parent_layout_type =
+        parent.and_then(|parent| parent.layout_type(store)).unwrap_or_default();
+    let layout_type = node.layout_type(store).unwrap_or_default();
+
+    let child_before =
+        parent.and_then(|parent| parent.child_before(store, dir)).unwrap_or_default();
+    let child_after = parent.and_then(|parent| parent.child_after(store, dir)).unwrap_or_default();
+    let row_col_between =
+        parent.and_then(|parent| parent.row_col_between(store, dir)).unwrap_or_default();
+    let mut before = node.before(store, dir).unwrap_or_default();
+    let mut after = node.after(store, dir).unwrap_or_default();
+    let min_before = node.min_before(store, dir).unwrap_or_default().value_or(0.0, -f32::MAX);
+    let max_before = node.max_before(store, dir).unwrap_or_default().value_or(f32::MAX, f32::MAX);
+    let min_after = node.min_after(store, dir).unwrap_or_default().value_or(0.0, -f32::MAX);
+    let max_after = node.max_after(store, dir).unwrap_or_default().value_or(f32::MAX, f32::MAX);
+    let (min_before, max_before) = inline_bounds(node.before2(store, dir), min_before, max_before);
+    let (min_after, max_after) = inline_bounds(node.after2(store, dir), min_after, max_after);
+    let size = node.size(store, dir).unwrap_or(options.default_size);
+
+    // The width of a node is determined before its height, unless it depends on the size of the parent
+    let width = node.size(store, Direction::X).unwrap_or(options.default_size);
+    let cross_size = if dir == Direction::Y && (width.is_pixels() || width.is_auto()) {
+        Some(cache.new_size(node, Direction::X))
+    } else {
+        fixed_size(node, store, !dir, None, options)
+    };
+    let size =
+        aspect_ratio_size(node, store, dir, cross_size, options).map(Units::Pixels).unwrap_or(size);
+
+    // integrate content_width and content_height into the child max and sum
+    // this means that if a node has both content and children (weird!) they should overlap
+    let content_size = measure_content(store, cache, node, dir);
+    cache.set_child_size_max(node, cache.child_size_max(node, dir).max(content_size), dir);
+    cache.set_child_size_sum(node, cache.child_size_sum(node, dir).max(content_size), dir);
+
+    // If Auto, then set the minimum height to be at least the height_sum/height_max/col_max of the children (depending on layout type)
+    // where children which can shrink only take up their min size
+    let layout_size = cache.child_size_layout(node, dir, layout_type);
+    let auto_min_size = shrunk_size.map_or(layout_size, |size| size.min(layout_size));
+    let mut min_size = node.min_size(store, dir).unwrap_or_default().value_or(0.0, auto_min_size);
+    min_size = min_size.clamp(0.0, f32::MAX);
+
+    let mut max_size = node.max_size(store, dir).unwrap_or_default().value_or(f32::MAX, f32::MAX);
+    max_size = max_size.max(min_size);
+    let (min_size, max_size) = inline_bounds(node.size2(store, dir), min_size, max_size);
+
+    let border_before = node.border_before(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+    let border_after = node.border_after(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+
+    // If left/right/top/bottom are Auto then the parent child_left/child_right/child_top/child_bottom overrides them
+    // The override is also dependent on position in stack (first, last, other) and layout type
+    if let Some(layout_dir) = parent_layout_type.direction() {
+        if layout_dir == dir {
+            if before == Units::Auto {
+                if cache.stack_first_child(node) {
+                    before = child_before;
+                } else {
+                    before = row_col_between;
+                }
+            }
+            if after == Units::Auto && cache.stack_last_child(node) {
+                after = child_after;
+            }
+        } else {
+            if before == Units::Auto {
+                before = child_before;
+            }
+            if after == Units::Auto {
+                after = child_after;
+            }
+        }
+    } else if parent_layout_type == LayoutType::Overlay {
+        if before == Units::Auto {
+            before = child_before;
+        }
+        if after == Units::Auto {
+            after = child_after;
+        }
+    }
+
+    let mut new_before = 0.0;
+    let mut new_size = 0.0;
+    let mut new_after = 0.0;
+    let mut used_space = 0.0;
+
+    // Stretch space and size is only known once the parent is laid out in step 3,
+    // so for now it takes up its minimum
+    match before {
+        Units::Pixels(val) => {
+            new_before = val.clamp(min_before, max_before);
+            used_space += new_before;
+        }
+
+        Units::Stretch(_) => {
+            new_before = min_before.clamp(0.0, f32::MAX);
+            used_space += new_before;
+        }
+
+        _ => {}
+    }
+
+    match size {
+        Units::Pixels(val) => {
+            new_size = val.clamp(min_size, max_size);
+            used_space += new_size;
+        }
+
+        Units::Auto => {
+            new_size = cache.child_size_layout(node, dir, layout_type);
+
+            new_size = new_size.clamp(min_size, max_size);
+
+            new_size += border_before + border_after;
+
+            used_space += new_size;
+        }
+
+        Units::Stretch(_) => {
+            new_size = min_size;
+            used_space += new_size;
+        }
+
+        _ => {}
+    }
+
+    match after {
+        Units::Pixels(val) => {
+            new_after = val.clamp(min_after, max_after);
+            used_space += new_after;
+        }
+
+        Units::Stretch(_) => {
+            new_after = min_after.clamp(0.0, f32::MAX);
+            used_space += new_after;
+        }
+
+        _ => {}
+    }
+
+    let position_type = node.position_type(store).unwrap_or_default();
+
+    cache.set_new_size(node, new_size, dir);
+    cache.set_before(node, new_before, dir);
+    cache.set_after(node, new_after, dir);
+
+    match parent_layout_type {
+        LayoutType::Column | LayoutType::Row | LayoutType::Overlay => {
+            if let Some(parent) = parent {
+                if position_type == PositionType::ParentDirected {
+                    cache.set_child_size_sum(
+                        parent,
+                        cache.child_size_sum(parent, dir) + used_space,
+                        dir,
+                    );
+
+                    cache.set_child_size_max(
+                        parent,
+                        used_space.max(cache.child_size_max(parent, dir)),
+                        dir,
+                    );
+                }
+            }
+        }
+
+        // The children of a grid are measured per track by `step2_grid` once the grid itself is reached
+        LayoutType::Grid => {}
+    }
+}
+
+/// Determine the size of the tracks of a grid node from the children placed in them
+///
+/// The total size of the tracks, including any pixel gutters, is stored in the cache and used as the content size
+/// of the grid when its width or height is Auto. Pixel tracks keep their size while all other tracks grow to fit
+/// the space used by their children.
+pub fn step2_grid<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    dir: Direction,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let grid_tracks = grid_track_list(node, cache, hierarchy, store, dir);
+
+    let child_before = node.child_before(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+    let child_after = node.child_after(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+    let row_col_between = node.row_col_between(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+
+    // While measuring an Auto grid only pixel tracks have a known size
+    let mut tracks = grid_tracks
+        .iter()
+        .map(|track| match track.size {
+            Units::Pixels(val) => (TrackSizing::Fixed, val),
+            _ => (TrackSizing::Measured, 0.0),
+        })
+        .collect::<Vec<_>>();
+
+    fit_grid_tracks(node, cache, hierarchy, store, dir, &mut tracks, row_col_between);
+
+    // Percentage bounds can't be resolved before the size of the grid is known and so are ignored
+    for (track, (_, size)) in grid_tracks.iter().zip(tracks.iter_mut()) {
+        let bounds =
+            GridTrack::minmax(pixels_or_auto(track.min), track.size, pixels_or_auto(track.max));
+        *size = bounds.clamp(*size, 0.0);
+    }
+
+    let gutters = grid_tracks.len().saturating_sub(1) as f32 * row_col_between;
+    let grid_size =
+        child_before + tracks.iter().map(|track| track.1).sum::<f32>() + gutters + child_after;
+
+    let content_size = measure_content(store, cache, node, dir);
+
+    cache.set_grid_row_col_max(node, grid_size.max(content_size), dir);
+}
+
+/// Determine the content size of a wrapping Row or Column node from the lines its children are split into
+///
+/// The children can only be split into lines when the size of the node along its layout direction is known by this
+/// step, which is when it is in pixels or when it is Auto with a maximum in pixels. The content size is then the size
+/// of the longest line along the layout direction and the total size of the lines and the gaps between them across it.
+/// A stretch or percentage size is only known once the parent is laid out in step 3, so until then the node takes up
+/// at least its widest child along the layout direction, and its lines are measured again against the resolved size.
+pub fn step2_wrap<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let dir = match node.layout_type(store).unwrap_or_default().direction() {
+        Some(dir) => dir,
+        None => return,
+    };
+
+    let border_before = node.border_before(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+    let border_after = node.border_after(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+
+    let content_size = match node.size(store, dir).unwrap_or(options.default_size) {
+        Units::Pixels(val) => {
+            wrap_content_size(node, cache, hierarchy, store, val - border_before - border_after)
+        }
+        Units::Auto => match node.max_size(store, dir).unwrap_or_default() {
+            Units::Pixels(val) => {
+                wrap_content_size(node, cache, hierarchy, store, val - border_before - border_after)
+            }
+            _ => return,
+        },
+        _ => {
+            let widest = wrap_content_size(node, cache, hierarchy, store, 0.0);
+            let line = wrap_content_size(node, cache, hierarchy, store, f32::MAX);
+            widest.zip(line).map(|((main_size, _), (_, cross_size))| (main_size, cross_size))
+        }
+    };
+
+    // These are used as the content size of the node when its width or height is Auto
+    if let Some((main_size, cross_size)) = content_size {
+        cache.set_child_size_sum(node, main_size, dir);
+        cache.set_child_size_max(node, cross_size, !dir);
+    }
+}
+
+// Returns the content size of a wrapping Row or Column node along and across its layout direction when its children
+// are split into lines no longer than the given size, if it has any parent-directed children
+fn wrap_content_size<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    cache: &C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    max_line_size: f32,
+) -> Option<(f32, f32)>
+where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let dir = node.layout_type(store).unwrap_or_default().direction()?;
+    let cross_dir = !dir;
+
+    let mut child_before = node.child_before(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+    let mut child_after = node.child_after(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+    let row_col_between = node.row_col_between(store, dir).unwrap_or_default().value_or(0.0, 0.0);
+
+    // The lines of a reversed stack start at the end of the node
+    if node.layout_reverse(store).unwrap_or_default() {
+        std::mem::swap(&mut child_before, &mut child_after);
+    }
+
+    let align_baseline = dir == Direction::X && node.align_baseline(store).unwrap_or_default();
+
+    // Each line is stored as the space it uses, the space after its last child, its size across the lines,
+    // and the space above and below the baselines of its children
+    let mut lines: Vec<(f32, f32, f32, (f32, f32))> = Vec::new();
+
+    hierarchy.child_iter(node, |child| {
+        let visible = is_shown(cache, child);
+        if !visible {
+            return;
+        }
+
+        let position_type = child.position_type(store).unwrap_or_default();
+        if position_type == PositionType::SelfDirected {
+            return;
+        }
+
+        // Auto space depends on the position of the child within its line
+        let before = match child.before(store, dir).unwrap_or_default() {
+            Units::Auto => None,
+            _ => Some(cache.before(child, dir)),
+        };
+        let after = match child.after(store, dir).unwrap_or_default() {
+            Units::Auto => None,
+            _ => Some(cache.after(child, dir)),
+        };
+        let size = cache.new_size(child, dir);
+
+        // Auto space across the layout direction is not taken from the child space of the node within a line
+        let mut cross_before = 0.0;
+        let mut cross_after = 0.0;
+        if child.before(store, cross_dir).unwrap_or_default() != Units::Auto {
+            cross_before = cache.before(child, cross_dir);
+        }
+        if child.after(store, cross_dir).unwrap_or_default() != Units::Auto {
+            cross_after = cache.after(child, cross_dir);
+        }
+        let cross_size = cross_before + cache.new_size(child, cross_dir) + cross_after;
+
+        // Children without a baseline take up no space around the baselines of the line
+        let mut extent = (0.0, 0.0);
+        if align_baseline {
+            let height = cache.new_size(child, cross_dir);
+            if let Some(child_extent) =
+                baseline_extent(child, store, cross_before, height, cross_after)
+            {
+                extent = child_extent;
+            }
+        }
+
+        // Auto space after the last child of a line is the child space of the node
+        let trailing = if after.is_some() { 0.0 } else { child_after };
+        let after = after.unwrap_or(0.0);
+
+        match lines.last_mut() {
+            Some((used, line_trailing, line_cross_size, line_extent))
+                if *used + before.unwrap_or(row_col_between) + size + after + trailing
+                    <= max_line_size =>
+            {
+                *used += before.unwrap_or(row_col_between) + size + after;
+                *line_trailing = trailing;
+                *line_cross_size = line_cross_size.max(cross_size);
+                *line_extent = (line_extent.0.max(extent.0), line_extent.1.max(extent.1));
+            }
+
+            _ => {
+                lines.push((
+                    before.unwrap_or(child_before) + size + after,
+                    trailing,
+                    cross_size,
+                    extent,
+                ));
+            }
+        }
+    });
+
+    if lines.is_empty() {
+        return None;
+    }
+
+    let line_before = node.child_before(store, cross_dir).unwrap_or_default().value_or(0.0, 0.0);
+    let line_after = node.child_after(store, cross_dir).unwrap_or_default().value_or(0.0, 0.0);
+    let line_between =
+        node.row_col_between(store, cross_dir).unwrap_or_default().value_or(0.0, 0.0);
+
+    let main_size = lines
+        .iter()
+        .map(|(used, trailing, _, _)| used + trailing)
+        .fold(0.0, |max: f32, size| max.max(size));
+    let cross_size = line_before
+        + lines
+            .iter()
+            .map(|(_, _, cross_size, (ascent, descent))| cross_size.max(ascent + descent))
+            .sum::<f32>()
+        + (lines.len() - 1) as f32 * line_between
+        + line_after;
+
+    Some((main_size, cross_size))
+}
+
+/// Determine the content height of a row node which aligns its children on their baselines
+///
+/// The children with a baseline take up the largest space above a baseline plus the largest space below a baseline.
+pub fn step2_baseline<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let mut ascent = 0.0f32;
+    let mut descent = 0.0f32;
+
+    hierarchy.child_iter(node, |child| {
+        let visible = is_shown(cache, child);
+        if !visible {
+            return;
+        }
+
+        let position_type = child.position_type(store).unwrap_or_default();
+        if position_type == PositionType::SelfDirected {
+            return;
+        }
+
+        let before = cache.before(child, Direction::Y);
+        let size = cache.new_size(child, Direction::Y);
+        let after = cache.after(child, Direction::Y);
+
+        if let Some((child_ascent, child_descent)) =
+            baseline_extent(child, store, before, size, after)
+        {
+            ascent = ascent.max(child_ascent);
+            descent = descent.max(child_descent);
+        }
+    });
+
+    cache.set_child_height_max(node, cache.child_height_max(node).max(ascent + descent));
+}
+
+pub fn step3_row_col<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    dir: Direction,
+    primary: bool,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let parent_layout_type = parent.layout_type(store).unwrap_or_default();
+    let parent_layout_reverse = parent.layout_reverse(store).unwrap_or_default();
+    let child_before = parent.child_before(store, dir).unwrap_or_default();
+    let child_after = parent.child_after(store, dir).unwrap_or_default();
+
+    let row_col_between = parent.row_col_between(store, dir).unwrap_or_default();
+
+    let mut parent_size = cache.new_size(parent, dir);
+    let parent_width_hard = cache.new_width(parent);
+
+    let parent_border_before =
         parent.border_before(store, dir).unwrap_or_default().value_or(parent_width_hard, 0.0);
     let parent_border_after =
         parent.border_after(store, dir).unwrap_or_default().value_or(parent_width_hard, 0.0);
@@ -314,39 +1290,590 @@ pub fn step3_row_col<'a, C, H>(
     parent_size -= parent_border_before + parent_border_after;
 
     let mut parent_free_space = parent_size;
-    let mut parent_stretch_sum = 0.0;
 
     let mut axis = SmallVec::<[ComputedData<<H as Hierarchy>::Item>; 3]>::new();
 
-    // ////////////////////////////////
-    // Calculate inflexible children //
-    ///////////////////////////////////
+    // Children which can shrink along the layout direction, along with their shrink factor and min size
+    let mut shrink_items = Vec::new();
+
+    // ////////////////////////////////
+    // Calculate inflexible children //
+    ///////////////////////////////////
+    hierarchy.child_iter(parent, |node| {
+        let visible = is_shown(cache, node);
+        if !visible {
+            return;
+        }
+
+        let layout_type = node.layout_type(store).unwrap_or_default();
+
+        let mut before = node.before(store, dir).unwrap_or_default();
+        let mut after = node.after(store, dir).unwrap_or_default();
+
+        let ((min_before, max_before), (min_after, max_after)) =
+            space_bounds(node, store, dir, parent_size);
+
+        let size = node.size(store, dir).unwrap_or(options.default_size);
+        let cross_size = match node.aspect_ratio(store) {
+            Some(_) if primary => Some(cross_size_before_layout(
+                store,
+                cache,
+                node,
+                parent,
+                !dir,
+                layout_type,
+                options,
+            )),
+            Some(_) => Some(cache.new_size(node, !dir)),
+            None => None,
+        };
+        let size = aspect_ratio_size(node, store, dir, cross_size, options)
+            .map(Units::Pixels)
+            .unwrap_or(size);
+
+        let auto_size = content_size_smart(store, cache, hierarchy, node, dir, primary, options);
+        let auto_min_size = shrunk_content_size(node, cache, hierarchy, store, dir)
+            .map_or(auto_size, |size| size.min(auto_size));
+
+        let mut min_size =
+            node.min_size(store, dir).unwrap_or_default().value_or(parent_size, auto_min_size);
+        min_size = min_size.clamp(0.0, f32::MAX);
+
+        let mut max_size = node
+            .max_size(store, dir)
+            .unwrap_or(Units::Pixels(f32::MAX))
+            .value_or(parent_size, auto_size);
+        max_size = max_size.max(min_size);
+        let (min_size, max_size) = inline_bounds(node.size2(store, dir), min_size, max_size);
+
+        let border_before =
+            node.border_before(store, dir).unwrap_or_default().value_or(parent_width_hard, 0.0);
+        let border_after =
+            node.border_after(store, dir).unwrap_or_default().value_or(parent_width_hard, 0.0);
+
+        let position_type = node.position_type(store).unwrap_or_default();
+
+        // Parent overrides
+        if let Some(layout_dir) = parent_layout_type.direction() {
+            if layout_dir == dir {
+                if before == Units::Auto {
+                    if cache.stack_first_child(node) {
+                        before = child_before;
+                    } else {
+                        before = row_col_between;
+                    }
+                }
+                if after == Units::Auto && cache.stack_last_child(node) {
+                    after = child_after;
+                }
+            } else {
+                if before == Units::Auto {
+                    before = child_before;
+                }
+                if after == Units::Auto {
+                    after = child_after;
+                }
+            }
+        }
+
+        let mut stretch_sum = 0.0;
+        let mut free_space = parent_size;
+
+        let first_item = axis.len();
+
+        let mut axis_space = AxisSpace {
+            node,
+            parent_size,
+            free_space: &mut free_space,
+            stretch_sum: &mut stretch_sum,
+            buffer: &mut axis,
+        };
+
+        let new_before = incorperate_axis(
+            before,
+            min_before,
+            max_before,
+            Axis::Before,
+            0.0,
+            &mut axis_space,
+            options,
+        );
+        let new_size = incorperate_axis(
+            size,
+            min_size,
+            max_size,
+            Axis::Size,
+            {
+                let auto = auto_size.clamp(min_size, max_size);
+                auto + border_before + border_after
+            },
+            &mut axis_space,
+            options,
+        );
+        let new_after = incorperate_axis(
+            after,
+            min_after,
+            max_after,
+            Axis::After,
+            0.0,
+            &mut axis_space,
+            options,
+        );
+
+        cache.set_new_size(node, new_size, dir);
+        cache.set_before(node, new_before, dir);
+        cache.set_after(node, new_after, dir);
+
+        if position_type == PositionType::ParentDirected {
+            parent_free_space -= parent_size - free_space;
+        }
+
+        cache.set_free_space(node, free_space, dir);
+        cache.set_stretch_sum(node, stretch_sum, dir);
+
+        // Stretch space and size against the layout direction, or of a self-directed child, only shares the free
+        // space left by the child itself and so can be resolved straight away
+        let shares_parent_space = position_type == PositionType::ParentDirected
+            && parent_layout_type.direction() == Some(dir);
+
+        if !shares_parent_space {
+            resolve_stretch_axis(&axis[first_item..], free_space, cache, dir, options);
+            axis.truncate(first_item);
+        } else {
+            let shrink = node.shrink(store).unwrap_or_default();
+            if shrink > 0.0 {
+                shrink_items.push((node, shrink, min_size));
+            }
+        }
+    });
+
+    /////////////////////////////////////
+    // Calculate flexible space & size //
+    /////////////////////////////////////
+    // The remaining stretch space and size of the children shares the free space of the parent
+    let stretch_space = resolve_stretch_axis(&axis, parent_free_space, cache, dir, options);
+
+    // Overflow along the layout direction is taken back from the children which can shrink, in proportion to their
+    // shrink factors and limited by their min size
+    let overflow = stretch_space - parent_free_space;
+    if overflow > 0.0 && !shrink_items.is_empty() {
+        let items = shrink_items
+            .iter()
+            .map(|&(node, shrink, min_size)| {
+                (shrink, 0.0, (cache.new_size(node, dir) - min_size).max(0.0))
+            })
+            .collect::<Vec<_>>();
+
+        for (&(node, _, _), reduction) in
+            shrink_items.iter().zip(resolve_stretch(overflow, &items, options))
+        {
+            cache.set_new_size(node, cache.new_size(node, dir) - reduction, dir);
+        }
+    }
+
+    let mut current_pos = 0.0;
+    let parent_pos = cache.pos(parent, dir) + parent_border_before;
+
+    ///////////////////////
+    // Position Children //
+    ///////////////////////
+    hierarchy.child_iter(parent, |node| {
+        let visible = is_shown(cache, node);
+        if !visible {
+            return;
+        }
+
+        let before = cache.before(node, dir);
+        let after = cache.after(node, dir);
+
+        let new_size = cache.new_size(node, dir);
+
+        let position_type = node.position_type(store).unwrap_or_default();
+
+        let new_pos = match position_type {
+            PositionType::SelfDirected => parent_pos + before,
+
+            PositionType::ParentDirected => {
+                let mut new_pos = parent_pos + current_pos + before;
+
+                if let Some(parent_layout_dir) = parent_layout_type.direction() {
+                    if parent_layout_dir == dir {
+                        // The children of a reversed stack are positioned back from the end of the parent
+                        if parent_layout_reverse {
+                            new_pos = parent_pos + parent_size - current_pos - after - new_size;
+                        }
+
+                        current_pos += before + new_size + after;
+                    }
+                }
+
+                new_pos
+            }
+        };
+
+        set_cell_layout(node, cache, dir, new_pos, new_size, options);
+    });
+}
+
+/// Hide the children of a collapsing Row or Column node which do not fit within it along the layout direction, and
+/// return true if any of them were hidden
+///
+/// While the parent-directed children overflow the node, the child with the lowest collapse priority is flagged as
+/// collapsed in the cache, with later children collapsing before earlier children of the same priority. Collapsed
+/// children are left out of layout like children which are not visible, while their visibility is left to the
+/// application. The space taken up by each child is the one determined by laying out the node along the layout
+/// direction, so the node is laid out again once children have collapsed. Children without a collapse priority are
+/// never hidden.
+pub fn step3_collapse<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    dir: Direction,
+) -> bool
+where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let (_, parent_size) = content_box(parent, cache, store, dir);
+
+    let mut used_space = 0.0;
+    let mut collapsible = Vec::new();
+
+    hierarchy.child_iter(parent, |node| {
+        let visible = is_shown(cache, node);
+        if !visible {
+            return;
+        }
+
+        let position_type = node.position_type(store).unwrap_or_default();
+        if position_type == PositionType::SelfDirected {
+            return;
+        }
+
+        let space = cache.before(node, dir) + cache.new_size(node, dir) + cache.after(node, dir);
+        used_space += space;
+
+        if let Some(priority) = node.collapse_priority(store) {
+            collapsible.push((priority, node, space));
+        }
+    });
+
+    // The sort is stable, so reversing first puts later children ahead of earlier ones with the same priority
+    collapsible.reverse();
+    collapsible.sort_by_key(|(priority, _, _)| *priority);
+
+    let mut collapsed_any = false;
+
+    for (_, node, space) in collapsible {
+        if used_space <= parent_size {
+            break;
+        }
+
+        cache.set_collapsed(node, true);
+        used_space -= space;
+        collapsed_any = true;
+    }
+
+    // The child space of the stack moves to the new first and last children
+    if collapsed_any {
+        set_stack_first_last(parent, cache, hierarchy, store);
+    }
+
+    collapsed_any
+}
+
+/// Position and size the children of a wrapping Row or Column node
+///
+/// Children are placed one after another along the layout direction until the next child does not fit, which then
+/// starts a new line. Each line is laid out as a stack of its own, with the child space of the node at either end of
+/// the line, and is as thick as its thickest child. The lines are separated by the row_between or col_between across
+/// the layout direction. Self-directed children are positioned within the borders of the node.
+pub fn step3_wrap<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    dir: Direction,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let cross_dir = !dir;
+
+    let (parent_pos, parent_size) = content_box(parent, cache, store, dir);
+
+    let child_before = parent.child_before(store, dir).unwrap_or_default();
+    let child_after = parent.child_after(store, dir).unwrap_or_default();
+    let row_col_between = parent.row_col_between(store, dir).unwrap_or_default();
+
+    // The lines of a reversed stack start at the end of the parent
+    let reverse = parent.layout_reverse(store).unwrap_or_default();
+    let (line_start, line_end) =
+        if reverse { (child_after, child_before) } else { (child_before, child_after) };
+
+    ///////////////////////////////
+    // Split children into lines //
+    ///////////////////////////////
+    let mut lines: Vec<Vec<WrapItem<<H as Hierarchy<'a>>::Item>>> = Vec::new();
+    let mut line_used = 0.0;
+
+    hierarchy.child_iter(parent, |node| {
+        let visible = is_shown(cache, node);
+        if !visible {
+            return;
+        }
+
+        let position_type = node.position_type(store).unwrap_or_default();
+        if position_type == PositionType::SelfDirected {
+            // The layout direction is determined first so that the other direction can depend on it
+            for &cell_dir in [dir, cross_dir].iter() {
+                let cell = content_box(parent, cache, store, cell_dir);
+                let cell = Cell::new(cell_dir, cell, cell_dir == dir);
+                let (new_pos, new_size) =
+                    layout_cell(node, parent, cache, hierarchy, store, cell, options);
+                set_cell_layout(node, cache, cell_dir, new_pos, new_size, options);
+            }
+
+            return;
+        }
+
+        let item = WrapItem::new(node, parent, cache, hierarchy, store, dir, options);
+
+        match lines.last_mut() {
+            Some(line)
+                if line_used
+                    + item.used_space((row_col_between, line_end), parent_size, options)
+                    <= parent_size =>
+            {
+                line_used += item.used_space((row_col_between, Units::Auto), parent_size, options);
+                line.push(item);
+            }
+
+            _ => {
+                line_used = item.used_space((line_start, Units::Auto), parent_size, options);
+                lines.push(vec![item]);
+            }
+        }
+    });
+
+    //////////////////////////////////////////////////
+    // Lay out each line along the layout direction //
+    //////////////////////////////////////////////////
+    for line in lines.iter() {
+        let mut free_space = parent_size;
+        let mut stretch_sum = 0.0;
+
+        let mut axis = SmallVec::<[ComputedData<<H as Hierarchy>::Item>; 3]>::new();
+
+        for (i, item) in line.iter().enumerate() {
+            let (first, last) = if reverse { (line.len() - 1, 0) } else { (0, line.len() - 1) };
+
+            let before = if i == first { child_before } else { row_col_between };
+            let after = if i == last { child_after } else { Units::Auto };
+
+            let (new_before, new_size, new_after) = item.incorperate(
+                (before, after),
+                parent_size,
+                &mut free_space,
+                &mut stretch_sum,
+                &mut axis,
+                options,
+            );
+
+            cache.set_before(item.node, new_before, dir);
+            cache.set_new_size(item.node, new_size, dir);
+            cache.set_after(item.node, new_after, dir);
+        }
+
+        resolve_stretch_axis(&axis, free_space, cache, dir, options);
+
+        let mut current_pos = 0.0;
+
+        for item in line.iter() {
+            let before = cache.before(item.node, dir);
+            let new_size = cache.new_size(item.node, dir);
+            let after = cache.after(item.node, dir);
+
+            let new_pos = if reverse {
+                parent_pos + parent_size - current_pos - after - new_size
+            } else {
+                parent_pos + current_pos + before
+            };
+
+            set_cell_layout(item.node, cache, dir, new_pos, new_size, options);
+
+            current_pos += before + new_size + after;
+        }
+    }
+
+    ///////////////////////////////////////////////////
+    // Lay out the lines across the layout direction //
+    ///////////////////////////////////////////////////
+    let align_baseline = dir == Direction::X && parent.align_baseline(store).unwrap_or_default();
+
+    // Each child is measured against an empty line, where stretch space and size take up their minimum
+    let line_sizes = lines
+        .iter()
+        .map(|line| {
+            let mut line_size = 0.0f32;
+            let mut ascent = 0.0f32;
+            let mut descent = 0.0f32;
+
+            for item in line.iter() {
+                let cell = Cell::new(cross_dir, (0.0, 0.0), false);
+                let (before, size) =
+                    layout_cell(item.node, parent, cache, hierarchy, store, cell, options);
+                let after = cache.after(item.node, cross_dir);
+
+                line_size = line_size.max(before + size + after);
+
+                if align_baseline {
+                    if let Some((item_ascent, item_descent)) =
+                        baseline_extent(item.node, store, before, size, after)
+                    {
+                        ascent = ascent.max(item_ascent);
+                        descent = descent.max(item_descent);
+                    }
+                }
+            }
+
+            (GridTrack::default(), line_size.max(ascent + descent))
+        })
+        .collect::<Vec<_>>();
+
+    let line_space = (
+        parent.child_before(store, cross_dir).unwrap_or_default(),
+        parent.row_col_between(store, cross_dir).unwrap_or_default(),
+        parent.child_after(store, cross_dir).unwrap_or_default(),
+    );
+
+    let line_cells = position_tracks(
+        content_box(parent, cache, store, cross_dir),
+        line_space,
+        &line_sizes,
+        options,
+    );
+
+    for (line, &cell) in lines.iter().zip(line_cells.iter()) {
+        for item in line.iter() {
+            let cell = Cell::new(cross_dir, cell, false);
+            let (new_pos, new_size) =
+                layout_cell(item.node, parent, cache, hierarchy, store, cell, options);
+            set_cell_layout(item.node, cache, cross_dir, new_pos, new_size, options);
+        }
+
+        if align_baseline {
+            let nodes = line.iter().map(|item| item.node).collect::<Vec<_>>();
+            align_baselines(&nodes, cache, store, options);
+        }
+    }
+}
+
+pub fn step3_grid<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    ////////////////////////////////////////
+    // Determine Size of rows and columns //
+    ////////////////////////////////////////
+    let col_tracks = grid_tracks(parent, cache, hierarchy, store, Direction::X, options);
+    let row_tracks = grid_tracks(parent, cache, hierarchy, store, Direction::Y, options);
+
+    ///////////////////////////////////////////////////
+    // Position and Size child nodes within the grid //
+    ///////////////////////////////////////////////////
     hierarchy.child_iter(parent, |node| {
-        let visible = cache.visible(node);
+        let visible = is_shown(cache, node);
         if !visible {
             return;
         }
 
-        let layout_type = node.layout_type(store).unwrap_or_default();
+        // Self-directed children ignore the tracks and are positioned within the borders of the grid
+        let (cellx, celly) = match node.position_type(store).unwrap_or_default() {
+            PositionType::SelfDirected => (
+                content_box(parent, cache, store, Direction::X),
+                content_box(parent, cache, store, Direction::Y),
+            ),
 
-        let mut before = node.before(store, dir).unwrap_or_default();
-        let mut after = node.after(store, dir).unwrap_or_default();
+            PositionType::ParentDirected => {
+                let row_start = cache.grid_row_index(node);
+                let row_end = row_start + cache.grid_row_col_span(node, Direction::Y).max(1) - 1;
+
+                let col_start = cache.grid_col_index(node);
+                let col_end = col_start + cache.grid_row_col_span(node, Direction::X).max(1) - 1;
+
+                let cell_posx = col_tracks[col_start].0;
+                let cell_width = col_tracks[col_end].0 + col_tracks[col_end].1 - cell_posx;
 
-        let min_before =
-            node.min_before(store, dir).unwrap_or_default().value_or(parent_size, -f32::MAX);
-        let max_before =
-            node.max_before(store, dir).unwrap_or_default().value_or(parent_size, f32::MAX);
-        let min_after =
-            node.min_after(store, dir).unwrap_or_default().value_or(parent_size, -f32::MAX);
-        let max_after =
-            node.max_after(store, dir).unwrap_or_default().value_or(parent_size, f32::MAX);
+                let cell_posy = row_tracks[row_start].0;
+                let cell_height = row_tracks[row_end].0 + row_tracks[row_end].1 - cell_posy;
 
-        let size = node.size(store, dir).unwrap_or(Units::Stretch(1.0));
+                ((cell_posx, cell_width), (cell_posy, cell_height))
+            }
+        };
+
+        // The width is determined first so that the height can depend on it
+        for &(dir, cell, primary) in
+            [(Direction::X, cellx, true), (Direction::Y, celly, false)].iter()
+        {
+            let cell = Cell::new(dir, cell, primary);
+            let (new_pos, new_size) =
+                layout_cell(node, parent, cache, hierarchy, store, cell, options);
+
+            set_cell_layout(node, cache, dir, new_pos, new_size, options);
+        }
+    });
+}
+
+// A child of a wrapping stack with its space and size along the layout direction of the stack
+struct WrapItem<N> {
+    node: N,
+    // The units, minimum, and maximum of the space before the child, its size, and the space after it
+    parts: [(Units, f32, f32); 3],
+    // The size of the child, including its borders, when its size is Auto
+    auto_size: f32,
+}
 
-        let auto_size = content_size_smart(store, cache, node, parent, dir, layout_type, primary);
+impl<N: Copy + for<'w> Node<'w>> WrapItem<N> {
+    fn new<'a, C, H>(
+        node: N,
+        parent: N,
+        cache: &mut C,
+        hierarchy: &'a H,
+        store: &'a <N as Node<'a>>::Data,
+        dir: Direction,
+        options: &LayoutOptions,
+    ) -> Self
+    where
+        C: Cache<Item = N>,
+        H: Hierarchy<'a, Item = N>,
+    {
+        let (_, parent_size) = content_box(parent, cache, store, dir);
+
+        let ((min_before, max_before), (min_after, max_after)) =
+            space_bounds(node, store, dir, parent_size);
+
+        let size = node.size(store, dir).unwrap_or(options.default_size);
+        let parent_cross_size = content_box(parent, cache, store, !dir).1;
+        let cross_size = fixed_size(node, store, !dir, Some(parent_cross_size), options);
+        let size = aspect_ratio_size(node, store, dir, cross_size, options)
+            .map(Units::Pixels)
+            .unwrap_or(size);
+
+        let auto_size = content_size_smart(store, cache, hierarchy, node, dir, true, options);
+        let auto_min_size = shrunk_content_size(node, cache, hierarchy, store, dir)
+            .map_or(auto_size, |size| size.min(auto_size));
 
         let mut min_size =
-            node.min_size(store, dir).unwrap_or_default().value_or(parent_size, auto_size);
+            node.min_size(store, dir).unwrap_or_default().value_or(parent_size, auto_min_size);
         min_size = min_size.clamp(0.0, f32::MAX);
 
         let mut max_size = node
@@ -354,648 +1881,1077 @@ pub fn step3_row_col<'a, C, H>(
             .unwrap_or(Units::Pixels(f32::MAX))
             .value_or(parent_size, auto_size);
         max_size = max_size.max(min_size);
+        let (min_size, max_size) = inline_bounds(node.size2(store, dir), min_size, max_size);
 
+        let parent_width_hard = cache.new_width(parent);
         let border_before =
             node.border_before(store, dir).unwrap_or_default().value_or(parent_width_hard, 0.0);
         let border_after =
             node.border_after(store, dir).unwrap_or_default().value_or(parent_width_hard, 0.0);
 
-        let position_type = node.position_type(store).unwrap_or_default();
-
-        // Parent overrides
-        if let Some(layout_dir) = parent_layout_type.direction() {
-            if layout_dir == dir {
-                if before == Units::Auto {
-                    if cache.stack_first_child(node) {
-                        before = child_before;
-                    } else {
-                        before = row_col_between;
-                    }
-                }
-                if after == Units::Auto {
-                    if cache.stack_last_child(node) {
-                        after = child_after;
-                    }
-                }
-            } else {
-                if before == Units::Auto {
-                    before = child_before;
-                }
-                if after == Units::Auto {
-                    after = child_after;
-                }
-            }
+        WrapItem {
+            node,
+            parts: [
+                (node.before(store, dir).unwrap_or_default(), min_before, max_before),
+                (size, min_size, max_size),
+                (node.after(store, dir).unwrap_or_default(), min_after, max_after),
+            ],
+            auto_size: auto_size.clamp(min_size, max_size) + border_before + border_after,
         }
+    }
 
-        let mut stretch_sum = 0.0;
-        let mut free_space = parent_size;
+    // Adds the space and size of the child to the free space and stretch sum of its line, where Auto space before
+    // and after the child is replaced by the given space, and returns the fixed values
+    fn incorperate(
+        &self,
+        space: (Units, Units),
+        parent_size: f32,
+        free_space: &mut f32,
+        stretch_sum: &mut f32,
+        axis: &mut SmallVec<[ComputedData<N>; 3]>,
+        options: &LayoutOptions,
+    ) -> (f32, f32, f32) {
+        let [(before, min_before, max_before), (size, min_size, max_size), (after, min_after, max_after)] =
+            self.parts;
+
+        let before = if before == Units::Auto { space.0 } else { before };
+        let after = if after == Units::Auto { space.1 } else { after };
+
+        let mut axis_space =
+            AxisSpace { node: self.node, parent_size, free_space, stretch_sum, buffer: axis };
 
         let new_before = incorperate_axis(
             before,
-            parent_size,
             min_before,
             max_before,
-            &mut free_space,
-            &mut stretch_sum,
             Axis::Before,
-            &mut axis,
-            node,
             0.0,
+            &mut axis_space,
+            options,
         );
         let new_size = incorperate_axis(
             size,
-            parent_size,
             min_size,
             max_size,
-            &mut free_space,
-            &mut stretch_sum,
             Axis::Size,
-            &mut axis,
-            node,
-            {
-                let auto = auto_size.clamp(min_size, max_size);
-                auto + border_before + border_after
-            },
+            self.auto_size,
+            &mut axis_space,
+            options,
         );
         let new_after = incorperate_axis(
             after,
-            parent_size,
             min_after,
             max_after,
-            &mut free_space,
-            &mut stretch_sum,
             Axis::After,
-            &mut axis,
-            node,
             0.0,
+            &mut axis_space,
+            options,
         );
 
-        cache.set_new_size(node, new_size, dir);
-        cache.set_before(node, new_before, dir);
-        cache.set_after(node, new_after, dir);
-
-        if position_type == PositionType::ParentDirected {
-            parent_free_space -= parent_size - free_space;
-            parent_stretch_sum += stretch_sum;
-        }
-
-        cache.set_free_space(node, free_space, dir);
-        cache.set_stretch_sum(node, stretch_sum, dir);
-    });
-
-    if parent_stretch_sum == 0.0 {
-        parent_stretch_sum = 1.0;
+        (new_before, new_size, new_after)
     }
 
-    // Sort the stretch elements in each axis by the maximum size
-    axis.sort_by(|a, b| b.min.partial_cmp(&a.min).unwrap());
-
-    let mut stretch_sum = 0.0;
-    let mut free_space = 0.0;
+    // Returns the space used by the child within a line, where stretch space and size take up their minimum
+    fn used_space(&self, space: (Units, Units), parent_size: f32, options: &LayoutOptions) -> f32 {
+        let mut free_space = 0.0;
+        let mut stretch_sum = 0.0;
+        let mut axis = SmallVec::new();
 
-    /////////////////////////////////////
-    // Calculate flexible space & size //
-    /////////////////////////////////////
-    for computed_data in axis.iter() {
-        let node = computed_data.node.clone();
+        self.incorperate(space, parent_size, &mut free_space, &mut stretch_sum, &mut axis, options);
 
-        let position_type = node.position_type(store).unwrap_or_default();
+        axis.iter().map(|computed_data| computed_data.min.max(0.0)).sum::<f32>() - free_space
+    }
+}
 
-        match position_type {
-            PositionType::SelfDirected => {
-                free_space = cache.free_space(node, dir);
-                stretch_sum = cache.stretch_sum(node, dir);
-            }
+/// Move the children of a row node down so that their baselines line up
+pub fn step3_baseline<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let mut nodes = Vec::new();
 
-            PositionType::ParentDirected => {
-                if let Some(parent_layout_dir) = parent_layout_type.direction() {
-                    if parent_layout_dir == dir {
-                        stretch_sum = parent_stretch_sum;
-                        free_space = parent_free_space;
-                    } else {
-                        free_space = cache.free_space(node, dir);
-                        stretch_sum = cache.stretch_sum(node, dir);
-                    }
-                }
-            }
+    hierarchy.child_iter(parent, |node| {
+        let visible = is_shown(cache, node);
+        if !visible {
+            return;
         }
 
-        // Prevent a divide by zero when the stretch sum is 0
-        if stretch_sum == 0.0 {
-            stretch_sum = 1.0;
+        if node.position_type(store).unwrap_or_default() == PositionType::ParentDirected {
+            nodes.push(node);
         }
+    });
 
-        // Compute the new left/width/height based on free space, stretch factor, and stretch_sum
-        #[cfg(feature = "rounding")]
-        let mut new_value = (free_space * computed_data.value / stretch_sum).round();
-        #[cfg(not(feature = "rounding"))]
-        let mut new_value = free_space * computed_data.value / stretch_sum;
+    align_baselines(&nodes, cache, store, options);
+}
 
-        // Clamp the new left/width/right to be between min_ left/width/right and max_ left/width/right
-        new_value = new_value.clamp(computed_data.min, computed_data.max);
+// Moves the nodes with a baseline down so that their baselines line up with the lowest of them
+fn align_baselines<'a, C, N>(
+    nodes: &[N],
+    cache: &mut C,
+    store: &'a N::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = N>,
+    N: Node<'a>,
+{
+    let baselines = nodes
+        .iter()
+        .map(|&node| {
+            node.baseline(store, cache.height(node)).map(|baseline| cache.posy(node) + baseline)
+        })
+        .collect::<Vec<_>>();
 
-        // Could perhaps replace this with a closure
-        match computed_data.axis {
-            Axis::Before => cache.set_before(node, new_value, dir),
-            Axis::Size => cache.set_new_size(node, new_value, dir),
-            Axis::After => cache.set_after(node, new_value, dir),
-        }
+    let lowest =
+        baselines.iter().flatten().fold(-f32::MAX, |max: f32, &baseline| max.max(baseline));
 
-        match position_type {
-            PositionType::SelfDirected => {
-                cache.set_stretch_sum(node, stretch_sum - computed_data.value, dir);
-                cache.set_free_space(node, free_space - new_value, dir);
-            }
+    for (&node, baseline) in nodes.iter().zip(baselines) {
+        if let Some(baseline) = baseline {
+            let offset = lowest - baseline;
 
-            PositionType::ParentDirected => {
-                if let Some(parent_layout_dir) = parent_layout_type.direction() {
-                    if parent_layout_dir == dir {
-                        parent_free_space -= new_value;
-                        parent_stretch_sum -= computed_data.value;
-                    } else {
-                        cache.set_stretch_sum(node, stretch_sum - computed_data.value, dir);
-                        cache.set_free_space(node, free_space - new_value, dir);
-                    }
-                }
+            let offset = options.round(offset);
+
+            if offset != 0.0 {
+                cache.set_geo_changed(node, GeometryChanged::POSY_CHANGED, true);
+                cache.set_posy(node, cache.posy(node) + offset);
             }
         }
     }
+}
 
-    let mut current_pos = 0.0;
-    let parent_pos = cache.pos(parent, dir) + parent_border_before;
+// Returns the space above and below the baseline of a node across a row, if the node has a baseline
+fn baseline_extent<'a, N: Node<'a>>(
+    node: N,
+    store: &'a N::Data,
+    before: f32,
+    size: f32,
+    after: f32,
+) -> Option<(f32, f32)> {
+    node.baseline(store, size).map(|baseline| (before + baseline, size - baseline + after))
+}
 
-    ///////////////////////
-    // Position Children //
-    ///////////////////////
+// Stores the position and size of a node laid out within a cell or a stack, flagging them if they have changed
+fn set_cell_layout<C: Cache>(
+    node: C::Item,
+    cache: &mut C,
+    dir: Direction,
+    new_pos: f32,
+    new_size: f32,
+    options: &LayoutOptions,
+) {
+    // The edges of the node are rounded rather than its position and size, so that neighbouring nodes meet
+    let (new_pos, new_size) = options.snap(new_pos, new_size);
+
+    if new_pos != cache.pos(node, dir) {
+        cache.set_geo_changed(node, GeometryChanged::pos_changed(dir), true);
+    }
+
+    if new_size != cache.size(node, dir) {
+        cache.set_geo_changed(node, GeometryChanged::size_changed(dir), true);
+    }
+
+    cache.set_pos(node, new_pos, dir);
+    cache.set_size(node, new_size, dir);
+    cache.set_new_size(node, new_size, dir);
+}
+
+/// Position and size the children of an overlay node on top of each other within the borders of the node
+///
+/// Each child is laid out in the same way as along the cross axis of a stack, in both directions.
+pub fn step3_overlay<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &mut C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    options: &LayoutOptions,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
     hierarchy.child_iter(parent, |node| {
-        let visible = cache.visible(node);
+        let visible = is_shown(cache, node);
         if !visible {
             return;
         }
 
-        let before = cache.before(node, dir);
-        let after = cache.after(node, dir);
-
-        let new_size = cache.new_size(node, dir);
-
-        let position_type = node.position_type(store).unwrap_or_default();
-
-        let new_pos = match position_type {
-            PositionType::SelfDirected => parent_pos + before,
-
-            PositionType::ParentDirected => {
-                let new_pos = parent_pos + current_pos + before;
-
-                if let Some(parent_layout_dir) = parent_layout_type.direction() {
-                    if parent_layout_dir == dir {
-                        current_pos += before + new_size + after;
-                    }
-                }
-
-                new_pos
-            }
-        };
+        // The width is determined first so that the height can depend on it
+        for &(dir, primary) in [(Direction::X, true), (Direction::Y, false)].iter() {
+            let cell = Cell::new(dir, content_box(parent, cache, store, dir), primary);
+            let (new_pos, new_size) =
+                layout_cell(node, parent, cache, hierarchy, store, cell, options);
 
-        if new_pos != cache.pos(node, dir) {
-            cache.set_geo_changed(node, GeometryChanged::pos_changed(dir), true);
+            set_cell_layout(node, cache, dir, new_pos, new_size, options);
         }
+    });
+}
 
-        if new_size != cache.size(node, dir) {
-            cache.set_geo_changed(node, GeometryChanged::size_changed(dir), true);
-        }
+// The position and size of a cell along one direction, and whether the direction is the one a child within the cell
+// is laid out in first
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    dir: Direction,
+    pos: f32,
+    size: f32,
+    primary: bool,
+}
 
-        cache.set_pos(node, new_pos, dir);
-        cache.set_size(node, new_size, dir);
-    });
+impl Cell {
+    fn new(dir: Direction, (pos, size): (f32, f32), primary: bool) -> Self {
+        Cell { dir, pos, size, primary }
+    }
 }
 
-pub fn step3_grid<'a, C, H>(
+// Computes the position and size of a child within a cell of its parent in the specified direction,
+// such as a grid cell or a line of a wrapping stack
+//
+// The space and size of the child are resolved in the same way as the cross axis of a stack,
+// with the cell taking the place of the parent.
+fn layout_cell<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
     parent: <H as Hierarchy<'a>>::Item,
     cache: &mut C,
     hierarchy: &'a H,
     store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
-) where
+    cell: Cell,
+    options: &LayoutOptions,
+) -> (f32, f32)
+where
     C: Cache<Item = <H as Hierarchy<'a>>::Item>,
     H: Hierarchy<'a>,
 {
-    let parent_width = cache.new_width(parent);
-    let parent_height = cache.new_height(parent);
+    let Cell { dir, pos: cell_pos, size: cell_size, primary } = cell;
+
+    let mut before = node.before(store, dir).unwrap_or_default();
+    let mut after = node.after(store, dir).unwrap_or_default();
+
+    // The child space of an overlay overrides Auto space in both directions
+    if parent.layout_type(store).unwrap_or_default() == LayoutType::Overlay {
+        if before == Units::Auto {
+            before = parent.child_before(store, dir).unwrap_or_default();
+        }
+        if after == Units::Auto {
+            after = parent.child_after(store, dir).unwrap_or_default();
+        }
+    }
 
-    /////////////////////////////////////////////////////
-    // Determine Size of non-flexible rows and columns //
-    /////////////////////////////////////////////////////
-    let grid_rows = parent.grid_rows(store).unwrap_or_default();
-    let grid_cols = parent.grid_cols(store).unwrap_or_default();
+    let ((min_before, max_before), (min_after, max_after)) =
+        space_bounds(node, store, dir, cell_size);
 
-    let mut row_heights = vec![(0.0, 0.0); 2 * grid_rows.len() + 2];
-    let mut col_widths = vec![(0.0, 0.0,); 2 * grid_cols.len() + 2];
+    let size = node.size(store, dir).unwrap_or(options.default_size);
+    let cross_size = if primary {
+        fixed_size(node, store, !dir, None, options)
+    } else {
+        Some(cache.new_size(node, !dir))
+    };
+    let size =
+        aspect_ratio_size(node, store, dir, cross_size, options).map(Units::Pixels).unwrap_or(size);
 
-    let row_heights_len = row_heights.len();
-    let col_widths_len = col_widths.len();
+    let auto_size = content_size_smart(store, cache, hierarchy, node, dir, primary, options);
+    let auto_min_size = shrunk_content_size(node, cache, hierarchy, store, dir)
+        .map_or(auto_size, |size| size.min(auto_size));
 
-    let mut col_free_space = parent_width;
-    let mut row_free_space = parent_height;
+    let mut min_size =
+        node.min_size(store, dir).unwrap_or_default().value_or(cell_size, auto_min_size);
+    min_size = min_size.clamp(0.0, f32::MAX);
 
-    let mut row_stretch_sum = 0.0;
-    let mut col_stretch_sum = 0.0;
+    let mut max_size =
+        node.max_size(store, dir).unwrap_or(Units::Pixels(f32::MAX)).value_or(cell_size, auto_size);
+    max_size = max_size.max(min_size);
+    let (min_size, max_size) = inline_bounds(node.size2(store, dir), min_size, max_size);
 
-    let row_between = parent.row_between(store).unwrap_or_default();
-    let col_between = parent.col_between(store).unwrap_or_default();
+    let border_before = node.border_before(store, dir).unwrap_or_default().value_or(cell_size, 0.0);
+    let border_after = node.border_after(store, dir).unwrap_or_default().value_or(cell_size, 0.0);
 
-    let child_left = parent.child_left(store).unwrap_or_default();
-    let child_right = parent.child_right(store).unwrap_or_default();
-    let child_top = parent.child_top(store).unwrap_or_default();
-    let child_bottom = parent.child_bottom(store).unwrap_or_default();
+    let mut free_space = cell_size;
+    let mut stretch_sum = 0.0;
 
-    match child_top {
-        Units::Pixels(val) => {
-            row_heights[0].1 = val;
-            row_free_space -= val;
-        }
+    let mut axis = SmallVec::<[ComputedData<<H as Hierarchy>::Item>; 3]>::new();
 
-        Units::Stretch(val) => {
-            row_stretch_sum += val;
+    let mut axis_space = AxisSpace {
+        node,
+        parent_size: cell_size,
+        free_space: &mut free_space,
+        stretch_sum: &mut stretch_sum,
+        buffer: &mut axis,
+    };
+
+    let mut new_before = incorperate_axis(
+        before,
+        min_before,
+        max_before,
+        Axis::Before,
+        0.0,
+        &mut axis_space,
+        options,
+    );
+    let mut new_size = incorperate_axis(
+        size,
+        min_size,
+        max_size,
+        Axis::Size,
+        auto_size.clamp(min_size, max_size) + border_before + border_after,
+        &mut axis_space,
+        options,
+    );
+    let mut new_after =
+        incorperate_axis(after, min_after, max_after, Axis::After, 0.0, &mut axis_space, options);
+
+    let stretch_items = axis
+        .iter()
+        .map(|computed_data| (computed_data.value, computed_data.min, computed_data.max))
+        .collect::<Vec<_>>();
+
+    for (computed_data, new_value) in
+        axis.iter().zip(resolve_stretch(free_space, &stretch_items, options))
+    {
+        match computed_data.axis {
+            Axis::Before => new_before = new_value,
+            Axis::Size => new_size = new_value,
+            Axis::After => new_after = new_value,
         }
-
-        _ => {}
     }
 
-    match child_bottom {
-        Units::Pixels(val) => {
-            row_heights[row_heights_len - 1].1 = val;
-            row_free_space -= val;
-        }
+    cache.set_before(node, new_before, dir);
+    cache.set_after(node, new_after, dir);
 
-        Units::Stretch(val) => {
-            row_stretch_sum += val;
-        }
+    (cell_pos + new_before, new_size)
+}
 
-        _ => {}
-    }
+// Computes the position and size of each of the grid tracks of a node in the specified direction,
+// within the borders of the node
+//
+// Pixel and percentage tracks have a fixed size, auto tracks fit the children placed in them, and stretch tracks,
+// gutters, and the space before and after the tracks share the remaining free space.
+fn grid_tracks<'a, C, H>(
+    parent: <H as Hierarchy<'a>>::Item,
+    cache: &C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    dir: Direction,
+    options: &LayoutOptions,
+) -> Vec<(f32, f32)>
+where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let (parent_pos, parent_size) = content_box(parent, cache, store, dir);
 
-    match child_left {
-        Units::Pixels(val) => {
-            col_widths[0].1 = val;
-            col_free_space -= val;
-        }
+    let grid_tracks = grid_track_list(parent, cache, hierarchy, store, dir);
 
-        Units::Stretch(val) => {
-            col_stretch_sum += val;
-        }
+    let child_before = parent.child_before(store, dir).unwrap_or_default();
+    let child_after = parent.child_after(store, dir).unwrap_or_default();
+    let row_col_between = parent.row_col_between(store, dir).unwrap_or_default();
 
-        _ => {}
+    let mut fitted_tracks = grid_tracks
+        .iter()
+        .map(|track| match track.size {
+            Units::Pixels(_) | Units::Percentage(_) | Units::Calc(_) => {
+                (TrackSizing::Fixed, fixed_grid_space(track.size, parent_size, options))
+            }
+            Units::Auto => (TrackSizing::Measured, 0.0),
+            Units::Stretch(_) => (TrackSizing::Flexible, 0.0),
+        })
+        .collect::<Vec<_>>();
+
+    let gutter = fixed_grid_space(row_col_between, parent_size, options);
+    fit_grid_tracks(parent, cache, hierarchy, store, dir, &mut fitted_tracks, gutter);
+
+    for (track, (_, size)) in grid_tracks.iter().zip(fitted_tracks.iter_mut()) {
+        *size = track.clamp(*size, parent_size);
     }
 
-    match child_right {
-        Units::Pixels(val) => {
-            col_widths[col_widths_len - 1].1 = val;
-            col_free_space -= val;
+    let tracks = grid_tracks
+        .iter()
+        .zip(fitted_tracks.iter())
+        .map(|(track, (_, size))| (*track, *size))
+        .collect::<Vec<_>>();
+
+    position_tracks(
+        (parent_pos, parent_size),
+        (child_before, row_col_between, child_after),
+        &tracks,
+        options,
+    )
+}
+
+// Positions tracks one after another within the space of a parent, given as its position and size, and returns the
+// position and size of each track
+//
+// The space is given as the space before the first track, between the tracks, and after the last track. Each track
+// is given with its size, which is replaced by a share of the free space for stretch tracks.
+fn position_tracks(
+    parent: (f32, f32),
+    space: (Units, Units, Units),
+    tracks: &[(GridTrack, f32)],
+    options: &LayoutOptions,
+) -> Vec<(f32, f32)> {
+    let (parent_pos, parent_size) = parent;
+    let (space_before, space_between, space_after) = space;
+
+    // The space before the first track, the tracks with the gaps between them, and the space after the last track
+    let mut spaces = Vec::with_capacity(2 * tracks.len() + 1);
+    spaces.push((
+        GridTrack::from(space_before),
+        fixed_grid_space(space_before, parent_size, options),
+    ));
+    for (i, track) in tracks.iter().enumerate() {
+        if i > 0 {
+            spaces.push((
+                GridTrack::from(space_between),
+                fixed_grid_space(space_between, parent_size, options),
+            ));
+        }
+        spaces.push(*track);
+    }
+    spaces
+        .push((GridTrack::from(space_after), fixed_grid_space(space_after, parent_size, options)));
+
+    let mut free_space = parent_size;
+    let mut stretch_items = Vec::new();
+
+    for (track, size) in spaces.iter() {
+        match track.size {
+            Units::Stretch(val) => stretch_items.push((
+                val,
+                track.clamp(-f32::MAX, parent_size),
+                track.clamp(f32::MAX, parent_size),
+            )),
+            _ => free_space -= size,
         }
+    }
 
-        Units::Stretch(val) => {
-            col_stretch_sum += val;
+    let mut stretch_sizes = resolve_stretch(free_space, &stretch_items, options).into_iter();
+
+    let mut positions = Vec::with_capacity(tracks.len());
+    let mut current_pos = parent_pos;
+
+    for (i, (track, size)) in spaces.iter().enumerate() {
+        let size = match track.size {
+            Units::Stretch(_) => stretch_sizes.next().unwrap_or_default(),
+            _ => *size,
+        };
+
+        // Tracks are at the odd indices, between the space before, the gaps, and the space after
+        if i % 2 == 1 {
+            positions.push((current_pos, size));
         }
 
-        _ => {}
+        current_pos += size;
     }
 
-    for (i, row) in grid_rows.iter().enumerate() {
-        let row_index = 2 * i + 1;
+    positions
+}
 
-        match row {
-            &Units::Pixels(val) => {
-                row_heights[row_index].1 = val;
-                row_free_space -= val;
-            }
+// Grow the measured tracks of a grid to fit the space used by the children placed in them
+//
+// Children which span several tracks spread any size they are missing evenly across the measured tracks they span,
+// unless one of the spanned tracks is flexible and will take up the difference.
+fn fit_grid_tracks<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    cache: &C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    dir: Direction,
+    tracks: &mut [(TrackSizing, f32)],
+    gutter: f32,
+) where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    // (start, end, used space) of the children spanning more than one track
+    let mut spanning = Vec::new();
 
-            &Units::Stretch(val) => {
-                row_stretch_sum += val;
-            }
+    hierarchy.child_iter(node, |child| {
+        let visible = is_shown(cache, child);
+        if !visible {
+            return;
+        }
 
-            _ => {}
+        let position_type = child.position_type(store).unwrap_or_default();
+        if position_type == PositionType::SelfDirected {
+            return;
         }
 
-        if i < grid_rows.len() - 1 {
-            let gutter_index = 2 * i + 2;
-            match row_between {
-                Units::Pixels(val) => {
-                    row_heights[gutter_index].1 = val;
-                    row_free_space -= val;
-                }
+        let start = cache.grid_row_col_index(child, dir);
+        let span = cache.grid_row_col_span(child, dir);
+        let end = (start + span).min(tracks.len());
+        if start >= end {
+            return;
+        }
 
-                Units::Stretch(val) => {
-                    row_stretch_sum += val;
-                }
+        let used_space =
+            cache.before(child, dir) + cache.new_size(child, dir) + cache.after(child, dir);
 
-                _ => {}
+        if end - start == 1 {
+            if tracks[start].0 == TrackSizing::Measured {
+                tracks[start].1 = tracks[start].1.max(used_space);
             }
+        } else {
+            spanning.push((start, end, used_space));
         }
-    }
+    });
 
-    for (i, col) in grid_cols.iter().enumerate() {
-        let col_index = 2 * i + 1;
-        match col {
-            &Units::Pixels(val) => {
-                col_widths[col_index].1 = val;
-                col_free_space -= val;
-            }
+    // Resolve the smaller spans first so that larger spans can take their sizes into account
+    spanning.sort_by_key(|&(start, end, _)| end - start);
 
-            &Units::Stretch(val) => {
-                col_stretch_sum += val;
-            }
+    for (start, end, used_space) in spanning {
+        let spanned = &mut tracks[start..end];
 
-            _ => {}
+        if spanned.iter().any(|track| track.0 == TrackSizing::Flexible) {
+            continue;
         }
 
-        if i < grid_cols.len() - 1 {
-            let gutter_index = 2 * i + 2;
-            match col_between {
-                Units::Pixels(val) => {
-                    col_widths[gutter_index].1 = val;
-                    col_free_space -= val;
-                }
+        let spanned_size =
+            spanned.iter().map(|track| track.1).sum::<f32>() + (end - start - 1) as f32 * gutter;
 
-                Units::Stretch(val) => {
-                    col_stretch_sum += val;
-                }
+        let measured_count =
+            spanned.iter().filter(|track| track.0 == TrackSizing::Measured).count();
 
-                _ => {}
+        if used_space > spanned_size && measured_count > 0 {
+            let share = (used_space - spanned_size) / measured_count as f32;
+            for track in spanned.iter_mut() {
+                if track.0 == TrackSizing::Measured {
+                    track.1 += share;
+                }
             }
         }
     }
+}
 
-    if row_stretch_sum == 0.0 {
-        row_stretch_sum = 1.0;
-    }
-    if col_stretch_sum == 0.0 {
-        col_stretch_sum = 1.0;
+// The largest number of tracks which a grid has in each direction beyond its explicit tracks
+const MAX_GRID_TRACKS: usize = 1000;
+
+// Returns the largest number of tracks a grid with the given number of explicit tracks has, including its implicit
+// tracks
+fn max_grid_tracks(track_count: usize) -> usize {
+    track_count.max(MAX_GRID_TRACKS)
+}
+
+// Resolves a grid index, where a negative index counts back from the end of the explicit tracks
+fn grid_index(index: isize, track_count: usize) -> usize {
+    if index < 0 {
+        (track_count as isize + index).max(0) as usize
+    } else {
+        index as usize
     }
+}
 
-    /////////////////////////////////////////////////
-    // Determine Size of flexible rows and columns //
-    /////////////////////////////////////////////////
+// Returns the position and size of the space within the borders of a node in the specified direction
+fn content_box<'a, C, N>(node: N, cache: &C, store: &'a N::Data, dir: Direction) -> (f32, f32)
+where
+    C: Cache<Item = N>,
+    N: Node<'a>,
+{
+    let width_hard = cache.new_width(node);
 
-    match child_top {
-        Units::Stretch(val) => {
-            #[cfg(feature = "rounding")]
-            {
-                row_heights[0].1 = (row_free_space * val / row_stretch_sum).round();
-            }
-            #[cfg(not(feature = "rounding"))]
-            {
-                row_heights[0].1 = row_free_space * val / row_stretch_sum;
-            }
-        }
+    let border_before =
+        node.border_before(store, dir).unwrap_or_default().value_or(width_hard, 0.0);
+    let border_after = node.border_after(store, dir).unwrap_or_default().value_or(width_hard, 0.0);
 
-        _ => {}
-    }
+    (cache.pos(node, dir) + border_before, cache.new_size(node, dir) - border_before - border_after)
+}
 
-    match child_bottom {
-        Units::Stretch(val) => {
-            #[cfg(feature = "rounding")]
-            {
-                row_heights[row_heights_len - 1].1 =
-                    (row_free_space * val / row_stretch_sum).round();
-            }
-            #[cfg(not(feature = "rounding"))]
-            {
-                row_heights[row_heights_len - 1].1 = row_free_space * val / row_stretch_sum;
-            }
-        }
+// Returns the explicit grid tracks of a node in the specified direction,
+// followed by the implicit tracks needed to hold any children placed after them
+fn grid_track_list<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    cache: &C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    dir: Direction,
+) -> Vec<GridTrack>
+where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let mut grid_tracks = node.grid_rows_cols(store, dir).unwrap_or_default();
 
-        _ => {}
-    }
+    let mut track_count = grid_tracks.len();
+    hierarchy.child_iter(node, |child| {
+        let visible = is_shown(cache, child);
+        if !visible {
+            return;
+        }
 
-    match child_left {
-        Units::Stretch(val) => {
-            #[cfg(feature = "rounding")]
-            {
-                col_widths[0].1 = (col_free_space * val / col_stretch_sum).round();
-            }
-            #[cfg(not(feature = "rounding"))]
-            {
-                col_widths[0].1 = col_free_space * val / col_stretch_sum;
-            }
+        let position_type = child.position_type(store).unwrap_or_default();
+        if position_type == PositionType::SelfDirected {
+            return;
         }
 
-        _ => {}
-    }
+        track_count = track_count
+            .max(cache.grid_row_col_index(child, dir) + cache.grid_row_col_span(child, dir));
+    });
 
-    match child_right {
-        Units::Stretch(val) => {
-            #[cfg(feature = "rounding")]
-            {
-                col_widths[col_widths_len - 1].1 = (col_free_space * val / col_stretch_sum).round();
-            }
-            #[cfg(not(feature = "rounding"))]
-            {
-                col_widths[col_widths_len - 1].1 = col_free_space * val / col_stretch_sum;
-            }
-        }
+    let implicit_track = node.grid_auto_rows_cols(store, dir).unwrap_or_default();
+    grid_tracks.resize(track_count, implicit_track);
 
-        _ => {}
-    }
+    grid_tracks
+}
 
-    let mut current_row_pos = cache.posy(parent) + row_heights[0].1;
-    let mut current_col_pos = cache.posx(parent) + col_widths[0].1;
+// Resolves stretch space and size which share the free space, stores the results in the cache, and returns the
+// total space taken up
+fn resolve_stretch_axis<C: Cache>(
+    axis: &[ComputedData<C::Item>],
+    free_space: f32,
+    cache: &mut C,
+    dir: Direction,
+    options: &LayoutOptions,
+) -> f32 {
+    let stretch_items = axis
+        .iter()
+        .map(|computed_data| (computed_data.value, computed_data.min, computed_data.max))
+        .collect::<Vec<_>>();
 
-    for (i, row) in grid_rows.iter().enumerate() {
-        let row_index = 2 * i + 1;
-        match row {
-            &Units::Stretch(val) => {
-                #[cfg(feature = "rounding")]
-                {
-                    row_heights[row_index].1 = (row_free_space * val / row_stretch_sum).round();
-                }
-                #[cfg(not(feature = "rounding"))]
-                {
-                    row_heights[row_index].1 = row_free_space * val / row_stretch_sum;
-                }
-            }
+    let mut used_space = 0.0;
 
-            _ => {}
+    for (computed_data, new_value) in
+        axis.iter().zip(resolve_stretch(free_space, &stretch_items, options))
+    {
+        match computed_data.axis {
+            Axis::Before => cache.set_before(computed_data.node, new_value, dir),
+            Axis::Size => cache.set_new_size(computed_data.node, new_value, dir),
+            Axis::After => cache.set_after(computed_data.node, new_value, dir),
         }
 
-        row_heights[row_index].0 = current_row_pos;
-        current_row_pos += row_heights[row_index].1;
+        used_space += new_value;
+    }
 
-        if i < grid_rows.len() - 1 {
-            let gutter_index = 2 * i + 2;
-            match row_between {
-                Units::Stretch(val) => {
-                    #[cfg(feature = "rounding")]
-                    {
-                        row_heights[gutter_index].1 =
-                            (row_free_space * val / row_stretch_sum).round();
-                    }
-                    #[cfg(not(feature = "rounding"))]
-                    {
-                        row_heights[gutter_index].1 = row_free_space * val / row_stretch_sum;
-                    }
-                }
+    used_space
+}
 
-                _ => {}
+// Shares the free space between stretch items in proportion to their stretch factors,
+// where each item is given as its stretch factor, minimum size, and maximum size
+//
+// Items whose share of the free space violates their min or max are frozen at the clamped size and the space which
+// is left over is shared again between the other items, until every item is frozen.
+fn resolve_stretch(
+    free_space: f32,
+    items: &[(f32, f32, f32)],
+    options: &LayoutOptions,
+) -> Vec<f32> {
+    let mut sizes = vec![0.0; items.len()];
+    let mut targets = vec![0.0; items.len()];
+    let mut frozen = vec![false; items.len()];
+
+    loop {
+        let mut remaining_space = free_space;
+        let mut stretch_sum = 0.0;
+
+        for (i, (factor, _, _)) in items.iter().enumerate() {
+            if frozen[i] {
+                remaining_space -= sizes[i];
+            } else {
+                stretch_sum += factor;
             }
+        }
 
-            row_heights[gutter_index].0 = current_row_pos;
-            current_row_pos += row_heights[gutter_index].1;
+        // Prevent a divide by zero when the stretch sum is 0
+        if stretch_sum == 0.0 {
+            stretch_sum = 1.0;
         }
-    }
-    let row_heights_len = row_heights.len() - 1;
-    row_heights[row_heights_len - 1].0 = current_row_pos;
 
-    for (i, col) in grid_cols.iter().enumerate() {
-        let col_index = 2 * i + 1;
+        let mut total_violation = 0.0;
 
-        match col {
-            &Units::Stretch(val) => {
-                #[cfg(feature = "rounding")]
-                {
-                    col_widths[col_index].1 = (col_free_space * val / col_stretch_sum).round();
-                }
-                #[cfg(not(feature = "rounding"))]
-                {
-                    col_widths[col_index].1 = col_free_space * val / col_stretch_sum;
-                }
+        for (i, &(factor, min, max)) in items.iter().enumerate() {
+            if !frozen[i] {
+                targets[i] = remaining_space * factor / stretch_sum;
+                sizes[i] = targets[i].min(max).max(min);
+                total_violation += sizes[i] - targets[i];
             }
-
-            _ => {}
         }
 
-        col_widths[col_index].0 = current_col_pos;
-        current_col_pos += col_widths[col_index].1;
-
-        if i < grid_cols.len() - 1 {
-            let gutter_index = 2 * i + 2;
-            match col_between {
-                Units::Stretch(val) => {
-                    #[cfg(feature = "rounding")]
-                    {
-                        col_widths[gutter_index].1 =
-                            (col_free_space * val / col_stretch_sum).round();
-                    }
-                    #[cfg(not(feature = "rounding"))]
-                    {
-                        col_widths[gutter_index].1 = col_free_space * val / col_stretch_sum;
-                    }
-                }
+        // Freeze the items clamped in the same direction as the total violation, or all items if there is none
+        let mut all_frozen = true;
 
-                _ => {}
+        for i in 0..items.len() {
+            if frozen[i] {
+                continue;
             }
 
-            col_widths[gutter_index].0 = current_col_pos;
-            current_col_pos += col_widths[gutter_index].1;
+            frozen[i] = if total_violation > 0.0 {
+                sizes[i] > targets[i]
+            } else if total_violation < 0.0 {
+                sizes[i] < targets[i]
+            } else {
+                true
+            };
+
+            all_frozen &= frozen[i];
         }
-    }
 
-    let col_widths_len = col_widths.len() - 1;
-    col_widths[col_widths_len - 1].0 = current_col_pos;
+        if all_frozen {
+            break;
+        }
+    }
 
-    ///////////////////////////////////////////////////
-    // Position and Size child nodes within the grid //
-    ///////////////////////////////////////////////////
-    hierarchy.child_iter(parent, |node| {
-        let visible = cache.visible(node);
-        if !visible {
-            return;
+    // The running total of the sizes is rounded rather than each size, so that the rounded sizes add up to the
+    // rounded free space and the items tile it without gaps or overlaps
+    if options.rounding != Rounding::None {
+        let mut total = 0.0;
+        let mut rounded_total = 0.0;
+
+        for size in sizes.iter_mut() {
+            total += *size;
+            let next_rounded_total = options.round(total);
+            *size = next_rounded_total - rounded_total;
+            rounded_total = next_rounded_total;
         }
+    }
 
-        let row_start = 2 * node.row_index(store).unwrap_or_default() + 1;
-        let row_span = 2 * node.row_span(store).unwrap_or(1) - 1;
-        let row_end = row_start + row_span;
+    sizes
+}
 
-        let col_start = 2 * node.col_index(store).unwrap_or_default() + 1;
-        let col_span = 2 * node.col_span(store).unwrap_or(1) - 1;
-        let col_end = col_start + col_span;
+// Keeps a bound of a grid track only if it is in pixels
+fn pixels_or_auto(units: Units) -> Units {
+    match units {
+        Units::Pixels(_) => units,
+        _ => Units::Auto,
+    }
+}
 
-        let new_posx = col_widths[col_start].0;
-        let new_width = col_widths[col_end].0 - new_posx;
+// Returns the index of the first grid track taken up by a child of a grid in the specified direction, if it has one,
+// along with the number of tracks it spans
+//
+// A named area or pair of named lines known to the grid takes precedence over the index and span of the child.
+fn grid_placement<'a, N: Node<'a>>(
+    parent: N,
+    node: N,
+    store: &'a N::Data,
+    dir: Direction,
+    track_count: usize,
+) -> (Option<usize>, usize) {
+    let area = node.grid_area(store).and_then(|name| grid_area_tracks(parent, store, &name, dir));
+
+    let lines = node.row_col_lines(store, dir).and_then(|(start, end)| {
+        let start = grid_line(parent, store, &start, dir)?;
+        let end = grid_line(parent, store, &end, dir)?;
+        Some((start.min(end), (start.max(end) - start.min(end)).max(1)))
+    });
 
-        let new_posy = row_heights[row_start].0;
-        let new_height = row_heights[row_end].0 - new_posy;
+    let (index, span) = match area.or(lines) {
+        Some((index, span)) => (Some(index), span),
+        None => (
+            node.row_col_index(store, dir).map(|index| grid_index(index, track_count)),
+            node.row_col_span(store, dir).unwrap_or(1).max(1),
+        ),
+    };
+
+    // The tracks taken up by the child are limited so that placing it far past the end of the grid does not add
+    // more implicit tracks than the grid can have
+    let max_tracks = max_grid_tracks(track_count);
+    let span = span.min(max_tracks);
+    (index.map(|index| index.min(max_tracks - span)), span)
+}
 
-        if new_posx != cache.posx(node) {
-            cache.set_geo_changed(node, GeometryChanged::POSX_CHANGED, true);
-        }
+// Returns the index of the first grid track taken up by a named area of a grid in the specified direction,
+// along with the number of tracks it spans
+fn grid_area_tracks<'a, N: Node<'a>>(
+    node: N,
+    store: &'a N::Data,
+    name: &str,
+    dir: Direction,
+) -> Option<(usize, usize)> {
+    let template = node.grid_template_areas(store)?;
+
+    let (first, last) = template
+        .iter()
+        .enumerate()
+        .flat_map(|(row, cells)| cells.iter().enumerate().map(move |(col, cell)| (row, col, cell)))
+        .filter(|(_, _, cell)| cell.as_str() == name && name != ".")
+        .map(|(row, col, _)| match dir {
+            Direction::X => col,
+            Direction::Y => row,
+        })
+        .fold(None, |range, index| match range {
+            Some((first, last)) => Some((index.min(first), index.max(last))),
+            None => Some((index, index)),
+        })?;
+
+    Some((first, last - first + 1))
+}
 
-        if new_posy != cache.posy(node) {
-            cache.set_geo_changed(node, GeometryChanged::POSY_CHANGED, true);
+// Returns the index of a named line of a grid in the specified direction
+//
+// Lines named by the grid are searched first, followed by the lines at the start and end of its named areas.
+fn grid_line<'a, N: Node<'a>>(
+    node: N,
+    store: &'a N::Data,
+    name: &str,
+    dir: Direction,
+) -> Option<usize> {
+    let named_line = node
+        .grid_row_col_lines(store, dir)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(line_name, _)| line_name == name)
+        .map(|(_, line)| line);
+
+    named_line.or_else(|| {
+        if let Some(area) = name.strip_suffix("-start") {
+            grid_area_tracks(node, store, area, dir).map(|(first, _)| first)
+        } else if let Some(area) = name.strip_suffix("-end") {
+            grid_area_tracks(node, store, area, dir).map(|(first, span)| first + span)
+        } else {
+            None
         }
+    })
+}
+
+// Records which cells of a grid are taken up by children, indexed by the major and then the minor direction
+#[derive(Default)]
+struct GridOccupancy {
+    cells: Vec<Vec<bool>>,
+}
 
-        if new_width != cache.width(node) {
-            cache.set_geo_changed(node, GeometryChanged::WIDTH_CHANGED, true);
+impl GridOccupancy {
+    fn is_free(&self, major: usize, minor: usize, major_span: usize, minor_span: usize) -> bool {
+        self.cells
+            .iter()
+            .skip(major)
+            .take(major_span)
+            .all(|line| line.iter().skip(minor).take(minor_span).all(|&occupied| !occupied))
+    }
+
+    fn insert(&mut self, major: usize, minor: usize, major_span: usize, minor_span: usize) {
+        if self.cells.len() < major + major_span {
+            self.cells.resize(major + major_span, Vec::new());
         }
 
-        if new_height != cache.height(node) {
-            cache.set_geo_changed(node, GeometryChanged::HEIGHT_CHANGED, true);
+        for line in self.cells[major..major + major_span].iter_mut() {
+            if line.len() < minor + minor_span {
+                line.resize(minor + minor_span, false);
+            }
+
+            for occupied in line[minor..minor + minor_span].iter_mut() {
+                *occupied = true;
+            }
         }
+    }
+}
+
+// Returns the size of grid tracks and spaces which do not depend on children or free space
+fn fixed_grid_space(units: Units, parent_size: f32, options: &LayoutOptions) -> f32 {
+    match units {
+        Units::Pixels(val) => val,
 
-        cache.set_posx(node, new_posx);
-        cache.set_posy(node, new_posy);
-        cache.set_width(node, new_width);
-        cache.set_height(node, new_height);
+        Units::Percentage(_) | Units::Calc(_) => options.round(units.value_or(parent_size, 0.0)),
 
-        cache.set_new_width(node, cache.width(node));
-        cache.set_new_height(node, cache.height(node));
-    });
+        _ => 0.0,
+    }
 }
 
-fn incorperate_axis<N: Clone + for<'w> Node<'w>>(
+fn incorperate_axis<N: for<'w> Node<'w>>(
     units: Units,
-    parent_size: f32,
     min: f32,
     max: f32,
-    free_space: &mut f32,
-    stretch_sum: &mut f32,
     axis: Axis,
-    axis_buf: &mut SmallVec<[ComputedData<N>; 3]>,
-    node: N,
     auto_size: f32,
+    space: &mut AxisSpace<N>,
+    options: &LayoutOptions,
 ) -> f32 {
     match units {
         Units::Pixels(val) => {
             let new = val.clamp(min, max);
-            *free_space -= new;
+            *space.free_space -= new;
             new
         }
 
-        Units::Percentage(val) => {
-            #[cfg(feature = "rounding")]
-            let new = ((val / 100.0) * parent_size).round();
-            #[cfg(not(feature = "rounding"))]
-            let new = (val / 100.0) * parent_size;
-            let new = new.clamp(min, max);
-            *free_space -= new;
+        Units::Percentage(_) | Units::Calc(_) => {
+            let new = options.round(units.value_or(space.parent_size, 0.0)).clamp(min, max);
+            *space.free_space -= new;
             new
         }
 
         Units::Stretch(val) => {
-            *stretch_sum += val;
-            axis_buf.push(ComputedData { node: node.clone(), value: val, min, max, axis });
+            *space.stretch_sum += val;
+            space.buffer.push(ComputedData { node: space.node, value: val, min, max, axis });
             0.0
         }
 
         Units::Auto => {
-            *free_space -= auto_size;
+            *space.free_space -= auto_size;
             auto_size
         }
     }
 }
 
-// this function is called to retrieve the auto size of a child
-fn content_size_smart<'a, C, N>(
+// Returns the min and max of the space before a node and of the space after it in the specified direction, where
+// percentages are of the parent size
+fn space_bounds<'a, N: Node<'a>>(
+    node: N,
     store: &'a N::Data,
-    cache: &mut C,
+    dir: Direction,
+    parent_size: f32,
+) -> ((f32, f32), (f32, f32)) {
+    let min_before =
+        node.min_before(store, dir).unwrap_or_default().value_or(parent_size, -f32::MAX);
+    let max_before =
+        node.max_before(store, dir).unwrap_or_default().value_or(parent_size, f32::MAX);
+    let min_after = node.min_after(store, dir).unwrap_or_default().value_or(parent_size, -f32::MAX);
+    let max_after = node.max_after(store, dir).unwrap_or_default().value_or(parent_size, f32::MAX);
+    (
+        inline_bounds(node.before2(store, dir), min_before, max_before),
+        inline_bounds(node.after2(store, dir), min_after, max_after),
+    )
+}
+
+// Narrows a min and max to the inline bounds of the units of a space or size, where the min takes precedence
+fn inline_bounds(units: Option<Units2>, min: f32, max: f32) -> (f32, f32) {
+    let (inline_min, inline_max) = units.unwrap_or_default().bounds();
+    let min = min.max(inline_min);
+    (min, max.min(inline_max).max(min))
+}
+
+// Returns the size of a node with an aspect ratio along the direction, derived from its size in the other direction,
+// if the size along the direction is the one to be derived
+fn aspect_ratio_size<'a, N: Node<'a>>(
+    node: N,
+    store: &'a N::Data,
+    dir: Direction,
+    cross_size: Option<f32>,
+    options: &LayoutOptions,
+) -> Option<f32> {
+    let ratio = node.aspect_ratio(store).filter(|ratio| *ratio > 0.0)?;
+
+    // A fixed size is never derived, and when neither size is fixed an Auto size is derived from a stretched one,
+    // otherwise the height is derived from the width
+    let width = node.size(store, Direction::X).unwrap_or(options.default_size);
+    let height = node.size(store, Direction::Y).unwrap_or(options.default_size);
+    let is_fixed = |units: Units| units.is_pixels() || units.is_percentage() || units.is_calc();
+    let derived_dir = match (width, height) {
+        (width, height) if is_fixed(width) && is_fixed(height) => return None,
+        (_, height) if is_fixed(height) => Direction::X,
+        (Units::Auto, Units::Stretch(_)) => Direction::X,
+        _ => Direction::Y,
+    };
+
+    if derived_dir != dir {
+        return None;
+    }
+
+    let size = match dir {
+        Direction::X => cross_size? * ratio,
+        Direction::Y => cross_size? / ratio,
+    };
+
+    Some(options.round(size))
+}
+
+// Returns the size of a node along the direction if it is in pixels, or depends only on a known parent size,
+// clamped by the min and max size of the node
+fn fixed_size<'a, N: Node<'a>>(
+    node: N,
+    store: &'a N::Data,
+    dir: Direction,
+    parent_size: Option<f32>,
+    options: &LayoutOptions,
+) -> Option<f32> {
+    let resolve = |units: Units| match (units, parent_size) {
+        (Units::Pixels(val), _) => Some(val),
+        (Units::Percentage(val), Some(parent_size)) => Some((val / 100.0) * parent_size),
+        (Units::Calc(calc), Some(parent_size)) => Some(calc.value(parent_size)),
+        _ => None,
+    };
+
+    let size = resolve(node.size(store, dir).unwrap_or(options.default_size))?;
+    let min = resolve(node.min_size(store, dir).unwrap_or_default()).unwrap_or(0.0);
+    let max = resolve(node.max_size(store, dir).unwrap_or_default()).unwrap_or(f32::MAX);
+    let (min, max) = inline_bounds(node.size2(store, dir), min, max);
+
+    Some(size.min(max).max(min))
+}
+
+// Computes the size of a node against the layout direction of its parent before the node is laid out in that direction
+// this is possible because we're going against the layout direction, so there are no
+// other elements that could be taking up stretch space
+fn cross_size_before_layout<'a, C, N>(
+    store: &'a N::Data,
+    cache: &C,
     node: N,
     parent: N,
     dir: Direction,
     layout_type: LayoutType,
-    primary: bool,
+    options: &LayoutOptions,
 ) -> f32
 where
     C: Cache<Item = N>,
     N: Node<'a>,
 {
+    let parent_size = cache.new_size(parent, dir);
+    let mut stretch_sum = 0.0;
+    let mut width_remaining = parent_size;
+    // TODO: min/max-before/after
+    match node.before(store, dir).unwrap_or_default() {
+        Units::Pixels(px) => width_remaining -= px,
+        Units::Percentage(p) => width_remaining -= p / 100.0 * parent_size,
+        Units::Calc(calc) => width_remaining -= calc.value(parent_size),
+        Units::Stretch(su) => stretch_sum += su,
+        Units::Auto => {} // auto before/after means 0
+    }
+    match node.size(store, dir).unwrap_or(options.default_size) {
+        Units::Pixels(px) => width_remaining -= px,
+        Units::Percentage(p) => width_remaining -= p / 100.0 * parent_size,
+        Units::Calc(calc) => width_remaining -= calc.value(parent_size),
+        Units::Stretch(su) => stretch_sum += su,
+        Units::Auto => width_remaining -= cache.child_size_layout(node, dir, layout_type),
+    }
+    match node.after(store, dir).unwrap_or_default() {
+        Units::Pixels(px) => width_remaining -= px,
+        Units::Percentage(p) => width_remaining -= p / 100.0 * parent_size,
+        Units::Calc(calc) => width_remaining -= calc.value(parent_size),
+        Units::Stretch(su) => stretch_sum += su,
+        Units::Auto => {}
+    }
+    if stretch_sum == 0.0 {
+        stretch_sum = 1.0;
+    }
+    let min = match node.min_size(store, dir).unwrap_or_default() {
+        Units::Pixels(px) => px,
+        Units::Percentage(p) => p / 100.0 * parent_size,
+        Units::Calc(calc) => calc.value(parent_size),
+        Units::Stretch(su) => width_remaining * su / stretch_sum,
+        Units::Auto => cache.child_size_layout(node, dir, layout_type),
+    }
+    .clamp(0.0, f32::MAX);
+    let max = match node.max_size(store, dir).unwrap_or(Units::Pixels(f32::MAX)) {
+        Units::Pixels(px) => px,
+        Units::Percentage(p) => p / 100.0 * parent_size,
+        Units::Calc(calc) => calc.value(parent_size),
+        Units::Stretch(su) => width_remaining * su / stretch_sum,
+        Units::Auto => cache.child_size_layout(node, dir, layout_type),
+    }
+    .clamp(min, f32::MAX);
+    let (min, max) = inline_bounds(node.size2(store, dir), min, max);
+    match node.size(store, dir).unwrap_or(options.default_size) {
+        Units::Pixels(v) => v,
+        Units::Percentage(p) => p / 100.0 * parent_size,
+        Units::Calc(calc) => calc.value(parent_size),
+        Units::Stretch(su) => width_remaining * su / stretch_sum,
+        Units::Auto => cache.child_size_layout(node, dir, layout_type),
+    }
+    .clamp(min, max)
+}
+
+// this function is called to retrieve the auto size of a child
+fn content_size_smart<'a, C, H>(
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    cache: &mut C,
+    hierarchy: &'a H,
+    node: <H as Hierarchy<'a>>::Item,
+    dir: Direction,
+    primary: bool,
+    options: &LayoutOptions,
+) -> f32
+where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    let layout_type = node.layout_type(store).unwrap_or_default();
     let basic_answer = cache.child_size_layout(node, dir, layout_type);
     // don't care about grids for now
     if layout_type == LayoutType::Grid {
@@ -1008,65 +2964,135 @@ where
         basic_answer
     } else {
         // secondary direction: hard case - need to compute secondary content-size
-        let other_dim = if !primary {
-            // easy case - we've already computed the other dimension
-            cache.size(node, !dir)
-        } else {
+        let other_dim = match hierarchy.parent(node) {
             // we need to pre-compute the other dimension
-            // this is possible because we're going against the layout direction, so there are no
-            // other elements that could be taking up stretch space
-
-            let parent_size = cache.new_size(parent, !dir);
-            let mut stretch_sum = 0.0;
-            let mut width_remaining = parent_size;
-            // TODO: min/max-before/after
-            match node.before(store, !dir).unwrap_or_default() {
-                Units::Pixels(px) => width_remaining -= px,
-                Units::Percentage(p) => width_remaining -= p / 100.0 * parent_size,
-                Units::Stretch(su) => stretch_sum += su,
-                Units::Auto => {} // auto before/after means 0
-            }
-            match node.size(store, !dir).unwrap_or_default() {
-                Units::Pixels(px) => width_remaining -= px,
-                Units::Percentage(p) => width_remaining -= p / 100.0 * parent_size,
-                Units::Stretch(su) => stretch_sum += su,
-                Units::Auto => width_remaining -= cache.child_size_layout(node, !dir, layout_type),
-            }
-            match node.after(store, !dir).unwrap_or_default() {
-                Units::Pixels(px) => width_remaining -= px,
-                Units::Percentage(p) => width_remaining -= p / 100.0 * parent_size,
-                Units::Stretch(su) => stretch_sum += su,
-                Units::Auto => {}
-            }
-            if stretch_sum == 0.0 {
-                stretch_sum = 1.0;
+            Some(parent) if primary => {
+                cross_size_before_layout(store, cache, node, parent, !dir, layout_type, options)
             }
-            let other_min = match node.min_size(store, !dir).unwrap_or_default() {
-                Units::Pixels(px) => px,
-                Units::Percentage(p) => p / 100.0 * parent_size,
-                Units::Stretch(su) => width_remaining * su / stretch_sum,
-                Units::Auto => cache.child_size_layout(node, !dir, layout_type),
-            }
-            .clamp(0.0, f32::MAX);
-            let other_max = match node.max_size(store, !dir).unwrap_or_default() {
-                Units::Pixels(px) => px,
-                Units::Percentage(p) => p / 100.0 * parent_size,
-                Units::Stretch(su) => width_remaining * su / stretch_sum,
-                Units::Auto => cache.child_size_layout(node, !dir, layout_type),
-            }
-            .clamp(other_min, f32::MAX);
-            let other_size = match node.size(store, !dir).unwrap_or_default() {
-                Units::Pixels(v) => v,
-                Units::Percentage(p) => p / 100.0 * parent_size,
-                Units::Stretch(su) => width_remaining * su / stretch_sum,
-                Units::Auto => cache.child_size_layout(node, !dir, layout_type),
+            // easy case - we've already computed the other dimension
+            _ => cache.size(node, !dir),
+        };
+
+        // The subtree is only measured again when the size it is measured against has changed
+        if let Some(measured) = cache.measured_size(node, dir, false) {
+            if measured.available == Some(other_dim) {
+                return measured.size;
             }
-            .clamp(other_min, other_max);
+        }
+
+        let computed = node.content_size_secondary(store, other_dim, dir).unwrap_or_default();
 
-            other_size
+        // The lines of a wrapping stack are measured against its size along the layout direction
+        let wrapped = if node.layout_wrap(store).unwrap_or_default() {
+            let border_before =
+                node.border_before(store, !dir).unwrap_or_default().value_or(0.0, 0.0);
+            let border_after =
+                node.border_after(store, !dir).unwrap_or_default().value_or(0.0, 0.0);
+            let max_line_size = other_dim - border_before - border_after;
+            wrap_content_size(node, cache, hierarchy, store, max_line_size)
+                .map_or(0.0, |(_, cross_size)| cross_size)
+        } else {
+            0.0
         };
 
-        let computed = node.content_size_secondary(store, other_dim, dir).unwrap_or_default();
-        computed.clamp(basic_answer, f32::MAX)
+        let size = computed.max(wrapped).clamp(basic_answer, f32::MAX);
+        cache.set_measured_size(
+            node,
+            dir,
+            false,
+            Some(MeasuredSize { available: Some(other_dim), size }),
+        );
+        size
+    }
+}
+
+// Returns the primary content size of a node, which is only measured when it has not been since the node was last
+// marked as dirty
+fn measure_content<'a, C, N>(store: &'a N::Data, cache: &mut C, node: N, dir: Direction) -> f32
+where
+    C: Cache<Item = N>,
+    N: Node<'a>,
+{
+    if let Some(measured) = cache.measured_size(node, dir, true) {
+        return measured.size;
+    }
+
+    let size = node.content_size(store, dir).unwrap_or_default();
+    cache.set_measured_size(node, dir, true, Some(MeasuredSize { available: None, size }));
+    size
+}
+
+// Clears the content sizes measured for a node, so that it is measured again
+fn clear_measured_size<C: Cache>(node: C::Item, cache: &mut C) {
+    for &dir in [Direction::X, Direction::Y].iter() {
+        cache.set_measured_size(node, dir, true, None);
+        cache.set_measured_size(node, dir, false, None);
+    }
+}
+
+// Returns the size taken up by the children of a Row or Column node along its layout direction when the children which
+// can shrink take up only their min size and the children which can collapse take up no space, if there are any
+//
+// This is used in place of the content size as the Auto min size of the node, so that the node does not grow to fit
+// children which would otherwise shrink or collapse to fit within it.
+fn shrunk_content_size<'a, C, H>(
+    node: <H as Hierarchy<'a>>::Item,
+    cache: &C,
+    hierarchy: &'a H,
+    store: &'a <<H as Hierarchy<'a>>::Item as Node<'a>>::Data,
+    dir: Direction,
+) -> Option<f32>
+where
+    C: Cache<Item = <H as Hierarchy<'a>>::Item>,
+    H: Hierarchy<'a>,
+{
+    if node.layout_type(store).unwrap_or_default().direction() != Some(dir)
+        || node.layout_wrap(store).unwrap_or_default()
+    {
+        return None;
+    }
+
+    let collapses = node.layout_collapse(store).unwrap_or_default();
+
+    let mut shrinks = false;
+    let mut used_space = 0.0;
+
+    hierarchy.child_iter(node, |child| {
+        let visible = is_shown(cache, child);
+        if !visible {
+            return;
+        }
+
+        let position_type = child.position_type(store).unwrap_or_default();
+        if position_type == PositionType::SelfDirected {
+            return;
+        }
+
+        if collapses && child.collapse_priority(store).is_some() {
+            shrinks = true;
+            return;
+        }
+
+        let mut size = cache.new_size(child, dir);
+
+        if child.shrink(store).unwrap_or_default() > 0.0 {
+            let layout_type = child.layout_type(store).unwrap_or_default();
+            let min_size = child
+                .min_size(store, dir)
+                .unwrap_or_default()
+                .value_or(0.0, cache.child_size_layout(child, dir, layout_type));
+            let (min_size, _) = inline_bounds(child.size2(store, dir), min_size.max(0.0), f32::MAX);
+
+            size = size.min(min_size);
+            shrinks = true;
+        }
+
+        used_space += cache.before(child, dir) + size + cache.after(child, dir);
+    });
+
+    if shrinks {
+        Some(used_space)
+    } else {
+        None
     }
 }
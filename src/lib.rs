@@ -26,6 +26,14 @@
 //!
 //! In this example the cache, tree, and a store for the node properties are kept in an ECS world struct and a node is simply an entity id.
 //!
+//! The rounding of the results and the size of nodes without a width or height can be chosen at runtime by calling
+//! [layout_with_options] with [LayoutOptions] instead.
+//!
+//! After a change to the properties of some nodes, they can be marked with [mark_dirty] and [relayout] will then only
+//! recalculate those nodes and the parts of the tree which depend on them. A single subtree can be laid out within a
+//! given position and size with [layout_subtree], leaving the rest of the tree as it is. Measured sizes are kept in the
+//! cache between calls, so a node whose content has changed must also be marked before it is laid out with [layout].
+//!
 //! See examples for details.
 //!
 //! # Layout system description
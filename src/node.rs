@@ -14,10 +14,60 @@ pub trait Node<'w>: Clone + Copy + std::fmt::Debug {
     /// - A Column layout type means that the child nodes will be positioned vertically one after another
     /// - A Grid layout type means that the children will be positioned based on the grid_rows and grid columns
     ///   as well as the child's row_index, col_index, row_span, and col_span properties.  
+    /// - An Overlay layout type means that the child nodes will be positioned on top of each other within the node
     fn layout_type(&self, store: &Self::Data) -> Option<LayoutType> {
         Some(LayoutType::Column)
     }
 
+    /// Get whether the children of a Row or Column node wrap onto new lines
+    ///
+    /// Children which do not fit on the current line along the layout direction move to a new line, with the lines
+    /// separated by the row_between or col_between across the layout direction.
+    fn layout_wrap(&self, store: &Self::Data) -> Option<bool> {
+        Some(false)
+    }
+
+    /// Get whether the children of a Row or Column node are laid out in reverse order
+    ///
+    /// The first child is placed at the end of the node along the layout direction, with the child space of the node
+    /// following the reversed order, while the order of the children in the tree is unchanged.
+    fn layout_reverse(&self, store: &Self::Data) -> Option<bool> {
+        Some(false)
+    }
+
+    /// Get whether the children of a Row node are aligned on their first baselines
+    ///
+    /// Children which have a baseline are moved down after the row is laid out so that their baselines line up with
+    /// the lowest baseline in the row, or within the line for a wrapping row. Other children are not moved.
+    fn align_baseline(&self, store: &Self::Data) -> Option<bool> {
+        Some(false)
+    }
+
+    /// Get whether the children of a Row or Column node collapse when they do not fit
+    ///
+    /// While the children overflow the node along the layout direction, the child with the lowest collapse priority
+    /// is hidden. Collapsed children are flagged in the cache so that they can be shown elsewhere, such as in an
+    /// overflow menu.
+    fn layout_collapse(&self, store: &Self::Data) -> Option<bool> {
+        Some(false)
+    }
+
+    /// Get the collapse priority of the node within a collapsing parent
+    ///
+    /// Children with a lower priority are collapsed first. Children without a collapse priority are never collapsed.
+    fn collapse_priority(&self, store: &Self::Data) -> Option<u32> {
+        None
+    }
+
+    /// Get whether the node is a layout boundary, whose size does not depend on its children
+    ///
+    /// A change within a layout boundary is not propagated to its ancestors by a partial relayout. Nodes with a fixed
+    /// size in both directions are detected as boundaries, so this only needs to be set on nodes which do not change
+    /// size for another reason.
+    fn layout_boundary(&self, store: &Self::Data) -> Option<bool> {
+        Some(false)
+    }
+
     /// Get the  position type of the node
     ///
     /// The position type of the node determines whether the node will be positioned in-line with its siblings or independently
@@ -35,6 +85,39 @@ pub trait Node<'w>: Clone + Copy + std::fmt::Debug {
         Some(Units::Stretch(1.0))
     }
 
+    /// Get the desired width of the node in units with an inline min and max
+    ///
+    /// The inline bounds apply in addition to the min_width and max_width of the node. Defaults to the width.
+    fn width2(&self, store: &'_ Self::Data) -> Option<Units2> {
+        self.width(store).map(Units2::from)
+    }
+
+    /// Get the desired height of the node in units with an inline min and max
+    ///
+    /// The inline bounds apply in addition to the min_height and max_height of the node. Defaults to the height.
+    fn height2(&self, store: &'_ Self::Data) -> Option<Units2> {
+        self.height(store).map(Units2::from)
+    }
+
+    /// Get the shrink factor of the node
+    ///
+    /// When the children of a Row or Column overflow it along the layout direction, the overflow is taken back from the
+    /// size of each child in proportion to its shrink factor, down to its min size. The children can only overflow a
+    /// parent with a min size below their total size, as an auto min size grows the parent to fit them. A factor of 0
+    /// means that the node does not shrink.
+    fn shrink(&self, store: &'_ Self::Data) -> Option<f32> {
+        Some(0.0)
+    }
+
+    /// Get the desired ratio of the width to the height of the node
+    ///
+    /// If only one of the width and height of the node is in pixels or a percentage then the other is derived from it,
+    /// otherwise an Auto size is derived from a Stretch size, or the height from the width. The derived size is then
+    /// clamped by its min and max.
+    fn aspect_ratio(&self, store: &'_ Self::Data) -> Option<f32> {
+        None
+    }
+
     /// Get the desired min_width of the node in units
     fn min_width(&self, store: &'_ Self::Data) -> Option<Units> {
         Some(Units::Auto)
@@ -75,6 +158,13 @@ pub trait Node<'w>: Clone + Copy + std::fmt::Debug {
         Some(0.0)
     }
 
+    /// Get the distance in pixels from the top of the node to the first baseline of its content, given the height of the node
+    ///
+    /// Returns None if the content of the node has no baseline, such as for a node without text.
+    fn baseline(&self, store: &'_ Self::Data, height: f32) -> Option<f32> {
+        None
+    }
+
     /// Get the desired space to the left of the node in units
     fn left(&self, store: &'_ Self::Data) -> Option<Units> {
         Some(Units::Auto)
@@ -92,6 +182,23 @@ pub trait Node<'w>: Clone + Copy + std::fmt::Debug {
         Some(Units::Auto)
     }
 
+    /// Get the desired space to the left of the node in units with an inline min and max, defaults to the left
+    fn left2(&self, store: &'_ Self::Data) -> Option<Units2> {
+        self.left(store).map(Units2::from)
+    }
+    /// Get the desired space to the right of the node in units with an inline min and max, defaults to the right
+    fn right2(&self, store: &'_ Self::Data) -> Option<Units2> {
+        self.right(store).map(Units2::from)
+    }
+    /// Get the desired space above the node in units with an inline min and max, defaults to the top
+    fn top2(&self, store: &'_ Self::Data) -> Option<Units2> {
+        self.top(store).map(Units2::from)
+    }
+    /// Get the desired space below the node in units with an inline min and max, defaults to the bottom
+    fn bottom2(&self, store: &'_ Self::Data) -> Option<Units2> {
+        self.bottom(store).map(Units2::from)
+    }
+
     /// Get the desired min_left of the node in units
     fn min_left(&self, store: &'_ Self::Data) -> Option<Units> {
         Some(Units::Auto)
@@ -164,23 +271,101 @@ pub trait Node<'w>: Clone + Copy + std::fmt::Debug {
         Some(Units::Auto)
     }
 
-    /// Get the desired grid rows as a vector of units
-    fn grid_rows(&self, store: &'_ Self::Data) -> Option<Vec<Units>> {
+    /// Get the desired grid rows as a vector of tracks
+    ///
+    /// Pixels and Percentage rows have a fixed height, Auto rows fit the children placed in them,
+    /// and Stretch rows share the remaining free space. The height of each row is clamped by its min and max.
+    fn grid_rows(&self, store: &'_ Self::Data) -> Option<Vec<GridTrack>> {
         Some(vec![])
     }
 
-    /// Get the desired grid columns as a vector of units
-    fn grid_cols(&self, store: &'_ Self::Data) -> Option<Vec<Units>> {
+    /// Get the desired grid columns as a vector of tracks
+    ///
+    /// Pixels and Percentage columns have a fixed width, Auto columns fit the children placed in them,
+    /// and Stretch columns share the remaining free space. The width of each column is clamped by its min and max.
+    fn grid_cols(&self, store: &'_ Self::Data) -> Option<Vec<GridTrack>> {
         Some(vec![])
     }
 
+    /// Get the grid auto flow of the node
+    ///
+    /// The auto flow determines how children without a row_index or col_index are placed within a grid.
+    /// If None then such children are placed in the first row or column.
+    fn grid_auto_flow(&self, store: &'_ Self::Data) -> Option<GridAutoFlow> {
+        None
+    }
+
+    /// Get the size of the implicit grid rows
+    ///
+    /// Implicit rows are added after the grid_rows when a child is placed beyond the last row.
+    fn grid_auto_rows(&self, store: &'_ Self::Data) -> Option<GridTrack> {
+        Some(GridTrack::default())
+    }
+
+    /// Get the size of the implicit grid columns
+    ///
+    /// Implicit columns are added after the grid_cols when a child is placed beyond the last column.
+    fn grid_auto_cols(&self, store: &'_ Self::Data) -> Option<GridTrack> {
+        Some(GridTrack::default())
+    }
+
+    /// Get the template of named areas of the grid, as the name of each cell by row and then by column
+    ///
+    /// An area takes up the rectangle of cells from the first to the last row and column in which its name appears.
+    /// A cell named "." is not part of any area. Each area also names the lines at its edges, e.g. an area named
+    /// "content" adds the lines "content-start" and "content-end" in both directions.
+    fn grid_template_areas(&self, store: &'_ Self::Data) -> Option<Vec<Vec<String>>> {
+        None
+    }
+
+    /// Get the names of the lines between grid rows, as pairs of a name and a line index
+    ///
+    /// Line 0 is before the first row and line n is after the nth row.
+    fn grid_row_lines(&self, store: &'_ Self::Data) -> Option<Vec<(String, usize)>> {
+        None
+    }
+
+    /// Get the names of the lines between grid columns, as pairs of a name and a line index
+    ///
+    /// Line 0 is before the first column and line n is after the nth column.
+    fn grid_col_lines(&self, store: &'_ Self::Data) -> Option<Vec<(String, usize)>> {
+        None
+    }
+
+    /// Get the name of the area of the parent grid in which the node is placed
+    ///
+    /// If the parent grid has an area with this name then it replaces the row and column index and span of the node.
+    fn grid_area(&self, store: &'_ Self::Data) -> Option<String> {
+        None
+    }
+
+    /// Get the names of the lines of the parent grid at which the rows taken up by the node start and end
+    ///
+    /// If the parent grid has lines with both names then they replace the row_index and row_span of the node.
+    fn row_lines(&self, store: &'_ Self::Data) -> Option<(String, String)> {
+        None
+    }
+
+    /// Get the names of the lines of the parent grid at which the columns taken up by the node start and end
+    ///
+    /// If the parent grid has lines with both names then they replace the col_index and col_span of the node.
+    fn col_lines(&self, store: &'_ Self::Data) -> Option<(String, String)> {
+        None
+    }
+
     /// Get the desired row_index of the node in units
-    fn row_index(&self, store: &'_ Self::Data) -> Option<usize> {
-        Some(0)
+    ///
+    /// A negative index counts back from the last of the parent's grid_rows, so -1 is the last row.
+    /// If None then the node is placed by the grid auto flow of its parent.
+    fn row_index(&self, store: &'_ Self::Data) -> Option<isize> {
+        None
     }
     /// Get the desired col_index of the node in units
-    fn col_index(&self, store: &'_ Self::Data) -> Option<usize> {
-        Some(0)
+    ///
+    /// A negative index counts back from the last of the parent's grid_cols, so -1 is the last column.
+    /// If None then the node is placed by the grid auto flow of its parent.
+    fn col_index(&self, store: &'_ Self::Data) -> Option<isize> {
+        None
     }
     fn row_span(&self, store: &'_ Self::Data) -> Option<usize> {
         Some(1)
@@ -203,9 +388,12 @@ pub trait Node<'w>: Clone + Copy + std::fmt::Debug {
 
     // these are "generic getters"
     fn size(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units> {
+        self.size2(store, axis).map(|units| units.units())
+    }
+    fn size2(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units2> {
         match axis {
-            Direction::X => self.width(store),
-            Direction::Y => self.height(store),
+            Direction::X => self.width2(store),
+            Direction::Y => self.height2(store),
         }
     }
     fn min_size(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units> {
@@ -238,15 +426,21 @@ pub trait Node<'w>: Clone + Copy + std::fmt::Debug {
         }
     }
     fn before(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units> {
+        self.before2(store, axis).map(|units| units.units())
+    }
+    fn before2(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units2> {
         match axis {
-            Direction::X => self.left(store),
-            Direction::Y => self.top(store),
+            Direction::X => self.left2(store),
+            Direction::Y => self.top2(store),
         }
     }
     fn after(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units> {
+        self.after2(store, axis).map(|units| units.units())
+    }
+    fn after2(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units2> {
         match axis {
-            Direction::X => self.right(store),
-            Direction::Y => self.bottom(store),
+            Direction::X => self.right2(store),
+            Direction::Y => self.bottom2(store),
         }
     }
     fn min_before(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units> {
@@ -291,22 +485,44 @@ pub trait Node<'w>: Clone + Copy + std::fmt::Debug {
             Direction::Y => self.row_between(store),
         }
     }
-    fn grid_rows_cols(&self, store: &'_ Self::Data, axis: Direction) -> Option<Vec<Units>> {
+    fn grid_rows_cols(&self, store: &'_ Self::Data, axis: Direction) -> Option<Vec<GridTrack>> {
         match axis {
-            Direction::X => self.grid_rows(store),
-            Direction::Y => self.grid_cols(store),
+            Direction::X => self.grid_cols(store),
+            Direction::Y => self.grid_rows(store),
         }
     }
-    fn row_col_index(&self, store: &'_ Self::Data, axis: Direction) -> Option<usize> {
+    fn grid_auto_rows_cols(&self, store: &'_ Self::Data, axis: Direction) -> Option<GridTrack> {
         match axis {
-            Direction::X => self.row_index(store),
-            Direction::Y => self.col_index(store),
+            Direction::X => self.grid_auto_cols(store),
+            Direction::Y => self.grid_auto_rows(store),
+        }
+    }
+    fn row_col_index(&self, store: &'_ Self::Data, axis: Direction) -> Option<isize> {
+        match axis {
+            Direction::X => self.col_index(store),
+            Direction::Y => self.row_index(store),
         }
     }
     fn row_col_span(&self, store: &'_ Self::Data, axis: Direction) -> Option<usize> {
         match axis {
-            Direction::X => self.row_span(store),
-            Direction::Y => self.col_span(store),
+            Direction::X => self.col_span(store),
+            Direction::Y => self.row_span(store),
+        }
+    }
+    fn grid_row_col_lines(
+        &self,
+        store: &'_ Self::Data,
+        axis: Direction,
+    ) -> Option<Vec<(String, usize)>> {
+        match axis {
+            Direction::X => self.grid_col_lines(store),
+            Direction::Y => self.grid_row_lines(store),
+        }
+    }
+    fn row_col_lines(&self, store: &'_ Self::Data, axis: Direction) -> Option<(String, String)> {
+        match axis {
+            Direction::X => self.col_lines(store),
+            Direction::Y => self.row_lines(store),
         }
     }
     fn border_before(&self, store: &'_ Self::Data, axis: Direction) -> Option<Units> {
@@ -1,6 +1,6 @@
 use bitflags::bitflags;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Direction {
     X,
     Y,
@@ -18,14 +18,17 @@ impl std::ops::Not for Direction {
 }
 
 /// The layout type determines how nodes will be positioned when directed by the parent
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum LayoutType {
     /// Stack child elements horizontally
     Row,
     /// Stack child elements vertically
+    #[default]
     Column,
     /// Position child elements into specified rows and columns
     Grid,
+    /// Position child elements on top of each other within the parent
+    Overlay,
 }
 
 impl LayoutType {
@@ -33,92 +36,203 @@ impl LayoutType {
         match self {
             LayoutType::Row => Some(Direction::X),
             LayoutType::Column => Some(Direction::Y),
-            LayoutType::Grid => None,
+            LayoutType::Grid | LayoutType::Overlay => None,
         }
     }
 }
 
-impl Default for LayoutType {
-    fn default() -> Self {
-        LayoutType::Column
-    }
-}
-
 /// The position type determines whether a node will be positioned in-line with its siblings or seperate
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum PositionType {
     /// Node is positioned relative to parent but ignores its siblings
     SelfDirected,
     /// Node is positioned relative to parent and in-line with siblings
+    #[default]
     ParentDirected,
 }
 
-impl Default for PositionType {
+/// The grid auto flow determines how the children of a grid without an explicit row or column index are placed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridAutoFlow {
+    /// Place children into the next free cell of the current row, moving on to the next row when it is full
+    Row,
+    /// Place children into the next free cell of the current column, moving on to the next column when it is full
+    Column,
+    /// Place each child into the first free cell of the grid by row, filling any gaps left by earlier children
+    RowDense,
+    /// Place each child into the first free cell of the grid by column, filling any gaps left by earlier children
+    ColumnDense,
+}
+
+impl GridAutoFlow {
+    /// Returns the direction in which the cells of a row or column are filled
+    pub fn direction(&self) -> Direction {
+        match self {
+            GridAutoFlow::Row | GridAutoFlow::RowDense => Direction::X,
+            GridAutoFlow::Column | GridAutoFlow::ColumnDense => Direction::Y,
+        }
+    }
+
+    /// Returns true if children are placed into the first free cell of the grid
+    pub fn is_dense(&self) -> bool {
+        matches!(self, GridAutoFlow::RowDense | GridAutoFlow::ColumnDense)
+    }
+}
+
+/// A grid row or column with a desired size which can be constrained by a minimum and maximum size
+///
+/// A minimum or maximum of Auto leaves the track unconstrained. Stretch tracks which are clamped by their minimum
+/// or maximum give up their share of the free space to the other stretch tracks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridTrack {
+    /// The desired size of the track
+    pub size: Units,
+    /// The minimum size of the track
+    pub min: Units,
+    /// The maximum size of the track
+    pub max: Units,
+}
+
+impl GridTrack {
+    /// Creates a track with the desired size which is constrained between a minimum and maximum size
+    pub fn minmax(min: Units, size: Units, max: Units) -> Self {
+        GridTrack { size, min, max }
+    }
+
+    /// Returns the track with the minimum size set
+    pub fn min(mut self, min: Units) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Returns the track with the maximum size set
+    pub fn max(mut self, max: Units) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Clamps a size of the track between its minimum and maximum, resolving percentages against the parent value
+    pub fn clamp(&self, size: f32, parent_value: f32) -> f32 {
+        let min = match self.min {
+            Units::Pixels(_) | Units::Percentage(_) | Units::Calc(_) => {
+                self.min.value_or(parent_value, 0.0)
+            }
+            _ => -f32::MAX,
+        };
+
+        let max = match self.max {
+            Units::Pixels(_) | Units::Percentage(_) | Units::Calc(_) => {
+                self.max.value_or(parent_value, 0.0)
+            }
+            _ => f32::MAX,
+        };
+
+        size.min(max).max(min)
+    }
+}
+
+impl Default for GridTrack {
     fn default() -> Self {
-        PositionType::ParentDirected
+        GridTrack::from(Units::Auto)
     }
 }
 
-/// Units which describe spacing and size
+impl From<Units> for GridTrack {
+    fn from(size: Units) -> Self {
+        GridTrack { size, min: Units::Auto, max: Units::Auto }
+    }
+}
+
+/// A number of pixels plus a percentage of the parent dimension, such as 100% minus 40 pixels
+///
+/// The resolved value can be clamped between a minimum and maximum number of pixels.
 #[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calc {
+    /// The number of pixels
+    pub pixels: f32,
+    /// The percentage of the parent dimension
+    pub percentage: f32,
+    /// The minimum number of pixels
+    pub min: f32,
+    /// The maximum number of pixels
+    pub max: f32,
+}
+
+impl Calc {
+    /// Creates a sum of a number of pixels and a percentage of the parent dimension
+    pub fn new(pixels: f32, percentage: f32) -> Self {
+        Calc { pixels, percentage, min: -f32::MAX, max: f32::MAX }
+    }
+
+    /// Returns the sum with the minimum number of pixels set
+    pub fn min(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Returns the sum with the maximum number of pixels set
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Resolves the sum against the parent value and clamps it between the minimum and maximum
+    pub fn value(&self, parent_value: f32) -> f32 {
+        (self.pixels + (self.percentage / 100.0) * parent_value).min(self.max).max(self.min)
+    }
+}
+
+/// Units which describe spacing and size
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Units {
     /// A number of pixels
     Pixels(f32),
     /// A percentage of the parent dimension
     Percentage(f32),
+    /// A number of pixels plus a percentage of the parent dimension
+    Calc(Calc),
     /// A factor of the remaining free space
     Stretch(f32),
     /// Automatically determine the value
+    #[default]
     Auto,
 }
 
-impl Default for Units {
-    fn default() -> Self {
-        Units::Auto
-    }
-}
-
 impl Units {
     /// Converts the units to an f32 value
     pub fn value_or(&self, parent_value: f32, auto: f32) -> f32 {
-        match self {
-            &Units::Pixels(pixels) => pixels,
-            &Units::Percentage(percentage) => (percentage / 100.0) * parent_value,
-            &Units::Stretch(_) => auto,
-            &Units::Auto => auto,
+        match *self {
+            Units::Pixels(pixels) => pixels,
+            Units::Percentage(percentage) => (percentage / 100.0) * parent_value,
+            Units::Calc(calc) => calc.value(parent_value),
+            Units::Stretch(_) => auto,
+            Units::Auto => auto,
         }
     }
 
     /// Returns true if the value is in pixels
     pub fn is_pixels(&self) -> bool {
-        match self {
-            Units::Pixels(_) => true,
-            _ => false,
-        }
+        matches!(self, Units::Pixels(_))
     }
 
     /// Returns true if the value is a percentage
     pub fn is_percentage(&self) -> bool {
-        match self {
-            Units::Percentage(_) => true,
-            _ => false,
-        }
+        matches!(self, Units::Percentage(_))
+    }
+
+    /// Returns true if the value is a sum of pixels and a percentage
+    pub fn is_calc(&self) -> bool {
+        matches!(self, Units::Calc(_))
     }
 
     /// Returns true if the value is a stretch factor
     pub fn is_stretch(&self) -> bool {
-        match self {
-            Units::Stretch(_) => true,
-            _ => false,
-        }
+        matches!(self, Units::Stretch(_))
     }
 
     /// Returns true if the value is auto
     pub fn is_auto(&self) -> bool {
-        match self {
-            Units::Auto => true,
-            _ => false,
-        }
+        matches!(self, Units::Auto)
     }
 }
 
@@ -142,6 +256,8 @@ bitflags! {
         const WIDTH_CHANGED  = 0b01000000;
         /// The height of the node has changed
         const HEIGHT_CHANGED = 0b10000000;
+        /// The position and size of the node need recalculating
+        const CHANGE = Self::CHANGE_POSX.bits | Self::CHANGE_POSY.bits | Self::CHANGE_WIDTH.bits | Self::CHANGE_HEIGHT.bits;
     }
 }
 
@@ -175,130 +291,240 @@ impl GeometryChanged {
     }
 }
 
-// WIP
-/*
-#[derive(Debug, Clone, Copy)]
+/// The content size of a node measured by a layout, along with the size available to it when it was measured
+///
+/// A measured size is reused by a layout or relayout while the node is not marked as dirty and the available size stays
+/// the same, so that expensive measurements such as of text are not repeated. The primary measurement stores the
+/// content of the node itself, while the secondary measurement stores the size of its subtree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasuredSize {
+    /// The size of the node in the other direction which the content was measured against, or None for the primary
+    /// measurement which does not depend on it
+    pub available: Option<f32>,
+    /// The measured size of the content
+    pub size: f32,
+}
+
+/// A value with a minimum and maximum which the resolved value is clamped between
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Value {
-    min: f32,
-    val: f32,
-    max: f32,
+    /// The minimum number of pixels
+    pub min: f32,
+    /// The desired value
+    pub val: f32,
+    /// The maximum number of pixels
+    pub max: f32,
 }
 
-const MIN: f32 = -std::f32::MAX;
-const MAX: f32 = std::f32::MAX;
+const MIN: f32 = -f32::MAX;
+const MAX: f32 = f32::MAX;
+
+impl Value {
+    fn new(val: f32) -> Self {
+        Value { min: MIN, val, max: MAX }
+    }
+
+    fn with_min(self, min: f32) -> Self {
+        Value { min: min.min(self.max), ..self }
+    }
 
+    fn with_max(self, max: f32) -> Self {
+        Value { min: self.min.min(max), max, ..self }
+    }
+}
 
-#[derive(Debug, Clone, Copy)]
+/// Units which carry their own minimum and maximum number of pixels
+///
+/// The bounds apply to the resolved value of the units, in addition to the min and max getters of a node, so that a
+/// single value such as `Units2::stretch(1.0).min(100.0).max(400.0)` describes a size together with its bounds.
+/// Auto units are always unbounded.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Units2 {
+    /// A number of pixels
     Pixels(Value),
+    /// A percentage of the parent dimension
     Percentage(Value),
+    /// A factor of the remaining free space
     Stretch(Value),
+    /// A number of pixels plus a percentage of the parent dimension
+    Calc(Calc),
+    /// Automatically determine the value
+    #[default]
     Auto,
 }
 
+impl From<Units> for Units2 {
+    fn from(units: Units) -> Self {
+        match units {
+            Units::Pixels(val) => Units2::pixels(val),
+            Units::Percentage(val) => Units2::percentage(val),
+            Units::Stretch(val) => Units2::stretch(val),
+            Units::Calc(calc) => Units2::Calc(calc),
+            Units::Auto => Units2::Auto,
+        }
+    }
+}
+
 impl Units2 {
+    /// Creates units of a number of pixels without a minimum or maximum
     pub fn pixels(val: f32) -> Self {
-        Self::Pixels(Value {min: MIN, val, max: MAX})
+        Self::Pixels(Value::new(val))
     }
 
+    /// Creates units of a percentage of the parent dimension without a minimum or maximum
     pub fn percentage(val: f32) -> Self {
-        Self::Pixels(Value {min: MIN, val, max: MAX})
+        Self::Percentage(Value::new(val))
     }
 
+    /// Creates units of a factor of the remaining free space without a minimum or maximum
     pub fn stretch(val: f32) -> Self {
-        Self::Pixels(Value {min: MIN, val, max: MAX})
+        Self::Stretch(Value::new(val))
     }
 
+    /// Creates Auto units
     pub fn auto() -> Self {
         Self::Auto
     }
 
+    /// Returns the units with the minimum number of pixels set
+    ///
+    /// A minimum above the maximum is lowered to the maximum, so that the maximum wins.
     pub fn min(self, min: f32) -> Self {
         match self {
-            Units2::Pixels(px) => {
-                assert!(min < px.max, "min must be less than max");
-                Units2::Pixels(Value {
-                    min,
-                    val: px.val,
-                    max: px.max,
-                })
-            }
-
-            Units2::Percentage(pc) => {
-                assert!(min < pc.max, "min must be less than max");
-                Units2::Percentage(Value {
-                    min,
-                    val: pc.val,
-                    max: pc.max,
-                })
-            }
+            Units2::Pixels(px) => Units2::Pixels(px.with_min(min)),
+            Units2::Percentage(pc) => Units2::Percentage(pc.with_min(min)),
+            Units2::Stretch(s) => Units2::Stretch(s.with_min(min)),
+            Units2::Calc(calc) => Units2::Calc(calc.min(min.min(calc.max))),
+            Units2::Auto => Units2::Auto,
+        }
+    }
 
-            Units2::Stretch(s) => {
-                assert!(min < s.max, "min must be less than max");
-                Units2::Stretch(Value {
-                    min,
-                    val: s.val,
-                    max: s.max,
-                })
-            }
+    /// Returns the units with the maximum number of pixels set
+    ///
+    /// A minimum above the new maximum is lowered to it, so that the maximum wins.
+    pub fn max(self, max: f32) -> Self {
+        match self {
+            Units2::Pixels(px) => Units2::Pixels(px.with_max(max)),
+            Units2::Percentage(pc) => Units2::Percentage(pc.with_max(max)),
+            Units2::Stretch(s) => Units2::Stretch(s.with_max(max)),
+            Units2::Calc(calc) => Units2::Calc(calc.max(max).min(calc.min.min(max))),
+            Units2::Auto => Units2::Auto,
+        }
+    }
 
-            Units2::Auto => {
-                Units2::Auto
-            }
+    /// Returns the units without their minimum and maximum
+    pub fn units(&self) -> Units {
+        match self {
+            Units2::Pixels(px) => Units::Pixels(px.val),
+            Units2::Percentage(pc) => Units::Percentage(pc.val),
+            Units2::Stretch(s) => Units::Stretch(s.val),
+            Units2::Calc(calc) => Units::Calc(*calc),
+            Units2::Auto => Units::Auto,
         }
     }
 
-    pub fn max(self, max: f32) -> Self {
+    /// Returns the minimum and maximum number of pixels
+    ///
+    /// The bounds of a Calc are applied when it is resolved and so are not returned here.
+    pub fn bounds(&self) -> (f32, f32) {
         match self {
-            Units2::Pixels(px) => {
-                assert!(max > px.min, "max must be greater than min");
-                Units2::Pixels(Value {
-                    min: px.min,
-                    val: px.val,
-                    max,
-                })
+            Units2::Pixels(value) | Units2::Percentage(value) | Units2::Stretch(value) => {
+                (value.min, value.max)
             }
 
-            Units2::Percentage(pc) => {
-                assert!(max > pc.min, "max must be greater than min");
-                Units2::Percentage(Value {
-                    min: pc.min,
-                    val: pc.val,
-                    max,
-                })
-            }
+            Units2::Calc(_) | Units2::Auto => (MIN, MAX),
+        }
+    }
 
-            Units2::Stretch(s) => {
-                assert!(max > s.min, "max must be greater than min");
-                Units2::Stretch(Value {
-                    min: s.min,
-                    val: s.val,
-                    max,
-                })
-            }
+    /// Clamps a resolved value between the minimum and maximum
+    pub fn clamp(&self, value: f32) -> f32 {
+        let (min, max) = self.bounds();
+        value.min(max).max(min)
+    }
+}
 
-            Units2::Auto => {
-                Units2::Auto
-            }
+/// The rounding applied to the positions and sizes computed by layout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rounding {
+    /// Positions and sizes are left unrounded
+    None,
+    /// The edges of nodes are rounded to whole physical pixels, with the rounding error of stretch space and size
+    /// shared between siblings
+    Pixels,
+}
+
+impl Default for Rounding {
+    fn default() -> Self {
+        if cfg!(feature = "rounding") {
+            Rounding::Pixels
+        } else {
+            Rounding::None
         }
     }
+}
 
-    pub fn clamp(&mut self) {
-        match self {
-            Units2::Pixels(px) => {
-                px.val = px.val.clamp(px.min, px.max);
-            }
+/// Options which set the policy of a layout calculation
+///
+/// Pixel units are logical pixels, which are multiplied by the scale factor to give physical pixels. The default
+/// options have a scale factor of 1, round to whole pixels when the `rounding` feature is enabled, and size nodes
+/// without a width or height with `Units::Stretch(1.0)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutOptions {
+    /// The rounding applied to the computed positions and sizes
+    pub rounding: Rounding,
+    /// The number of physical pixels per logical pixel
+    pub scale_factor: f32,
+    /// The size of nodes which do not have a width or height
+    pub default_size: Units,
+}
 
-            Units2::Percentage(pc) => {
-                pc.val = pc.val.clamp(pc.min, pc.max);
-            }
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        LayoutOptions {
+            rounding: Rounding::default(),
+            scale_factor: 1.0,
+            default_size: Units::Stretch(1.0),
+        }
+    }
+}
 
-            Units2::Stretch(s) => {
-                s.val = s.val.clamp(s.min, s.max);
-            }
+impl LayoutOptions {
+    /// Rounds a value in logical pixels to a whole number of physical pixels, if the options round
+    pub fn round(&self, value: f32) -> f32 {
+        match self.rounding {
+            Rounding::None => value,
+            Rounding::Pixels => (value * self.scale_factor).round() / self.scale_factor,
+        }
+    }
 
-            _=> {}
+    /// Rounds the edges of a node with a position and size in logical pixels, if the options round, and returns the
+    /// new position and size
+    pub fn snap(&self, pos: f32, size: f32) -> (f32, f32) {
+        match self.rounding {
+            Rounding::None => (pos, size),
+            Rounding::Pixels => {
+                let start = self.round(pos);
+                let end = self.round(pos + size);
+                (start, end - start)
+            }
         }
     }
+
+    /// Returns the options with the rounding set
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Returns the options with the scale factor set
+    pub fn scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Returns the options with the default size set
+    pub fn default_size(mut self, default_size: Units) -> Self {
+        self.default_size = default_size;
+        self
+    }
 }
-*/
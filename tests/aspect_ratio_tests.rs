@@ -0,0 +1,137 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of stretch and auto sizes which are derived from the other dimension of a node with an aspect ratio
+#[test]
+fn aspect_ratio_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(690.0));
+    world.set_height(row, Units::Pixels(500.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let avatar = world.add(Some(row));
+    world.set_width(avatar, Units::Auto);
+    world.set_height(avatar, Units::Pixels(50.0));
+    world.set_aspect_ratio(avatar, 1.0);
+
+    let video = world.add(Some(row));
+    world.set_width(video, Units::Stretch(1.0));
+    world.set_height(video, Units::Auto);
+    world.set_aspect_ratio(video, 16.0 / 9.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(avatar), 50.0);
+    assert_eq!(world.cache.height(avatar), 50.0);
+
+    assert_eq!(world.cache.posx(video), 50.0);
+    assert_eq!(world.cache.width(video), 640.0);
+    assert_eq!(world.cache.height(video), 360.0);
+}
+
+/// Test of sizes derived from an aspect ratio which are clamped by the min and max size of the node
+#[test]
+fn aspect_ratio_min_max() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(400.0));
+    world.set_height(column, Units::Pixels(600.0));
+
+    let child1 = world.add(Some(column));
+    world.set_width(child1, Units::Stretch(1.0));
+    world.set_height(child1, Units::Auto);
+    world.set_max_height(child1, Units::Pixels(150.0));
+    world.set_aspect_ratio(child1, 2.0);
+
+    let child2 = world.add(Some(column));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Stretch(1.0));
+    world.set_min_height(child2, Units::Pixels(250.0));
+    world.set_aspect_ratio(child2, 0.5);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 400.0);
+    assert_eq!(world.cache.height(child1), 150.0);
+
+    assert_eq!(world.cache.posy(child2), 150.0);
+    assert_eq!(world.cache.width(child2), 100.0);
+    assert_eq!(world.cache.height(child2), 250.0);
+}
+
+/// Test of auto width and height on a row which fits children with an aspect ratio
+#[test]
+fn aspect_ratio_auto_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Auto);
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(120.0));
+    world.set_height(child1, Units::Auto);
+    world.set_aspect_ratio(child1, 4.0 / 3.0);
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Auto);
+    world.set_height(child2, Units::Pixels(60.0));
+    world.set_aspect_ratio(child2, 2.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(row), 240.0);
+    assert_eq!(world.cache.height(row), 90.0);
+
+    assert_eq!(world.cache.height(child1), 90.0);
+    assert_eq!(world.cache.posx(child2), 120.0);
+    assert_eq!(world.cache.width(child2), 120.0);
+}
+
+/// Test of a height derived from a stretch width across a column, which an auto sized column grows to fit
+#[test]
+fn aspect_ratio_stretch_auto_parent() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(400.0));
+    world.set_height(column, Units::Auto);
+    world.set_layout_type(column, LayoutType::Column);
+
+    let image = world.add(Some(column));
+    world.set_width(image, Units::Stretch(1.0));
+    world.set_height(image, Units::Auto);
+    world.set_aspect_ratio(image, 2.0);
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(image), 400.0);
+    assert_eq!(world.cache.height(image), 200.0);
+
+    assert_eq!(world.cache.height(column), 200.0);
+    assert_eq!(world.cache.posy(sibling), 200.0);
+}
@@ -0,0 +1,131 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of the children of a row which are aligned on their baselines
+#[test]
+fn baseline_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_align_baseline(row, true);
+
+    let label = world.add(Some(row));
+    world.set_width(label, Units::Pixels(80.0));
+    world.set_height(label, Units::Pixels(20.0));
+    world.set_baseline(label, 15.0);
+
+    let input = world.add(Some(row));
+    world.set_width(input, Units::Pixels(150.0));
+    world.set_height(input, Units::Pixels(30.0));
+    world.set_baseline(input, 20.0);
+
+    let button = world.add(Some(row));
+    world.set_width(button, Units::Pixels(100.0));
+    world.set_height(button, Units::Pixels(40.0));
+    world.set_baseline(button, 25.0);
+
+    // A child without a baseline is not moved
+    let icon = world.add(Some(row));
+    world.set_width(icon, Units::Pixels(24.0));
+    world.set_height(icon, Units::Pixels(24.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posy(label), 10.0);
+    assert_eq!(world.cache.posy(input), 5.0);
+    assert_eq!(world.cache.posy(button), 0.0);
+    assert_eq!(world.cache.posy(icon), 0.0);
+
+    assert_eq!(world.cache.posx(input), 80.0);
+    assert_eq!(world.cache.height(input), 30.0);
+}
+
+/// Test of auto height on a row which fits the space above and below the baselines of its children
+#[test]
+fn baseline_row_auto_height() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_align_baseline(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(20.0));
+    world.set_baseline(child1, 16.0);
+    world.set_top(child1, Units::Pixels(10.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(50.0));
+    world.set_baseline(child2, 10.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(row), 66.0);
+
+    assert_eq!(world.cache.posy(child1), 10.0);
+    assert_eq!(world.cache.posy(child2), 16.0);
+}
+
+/// Test of a wrapping row which aligns the children within each line on their baselines
+#[test]
+fn baseline_wrap_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(220.0));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+    world.set_align_baseline(row, true);
+    world.set_col_between(row, Units::Pixels(10.0));
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(20.0));
+    world.set_baseline(child1, 15.0);
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(40.0));
+    world.set_baseline(child2, 10.0);
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(30.0));
+    world.set_baseline(child3, 25.0);
+
+    let child4 = world.add(Some(row));
+    world.set_width(child4, Units::Pixels(100.0));
+    world.set_height(child4, Units::Pixels(20.0));
+    world.set_baseline(child4, 5.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posy(child1), 0.0);
+    assert_eq!(world.cache.posy(child2), 5.0);
+
+    // The first line is as tall as the space above and below its baseline
+    assert_eq!(world.cache.posy(child3), 45.0);
+    assert_eq!(world.cache.posy(child4), 65.0);
+
+    assert_eq!(world.cache.height(row), 85.0);
+}
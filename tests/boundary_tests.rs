@@ -0,0 +1,305 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of a stretch container which is detected as a layout boundary, so that its ancestors are not recalculated
+#[test]
+fn boundary_stretch() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let container = world.add(Some(root));
+    world.set_min_width(container, Units::Pixels(0.0));
+    world.set_min_height(container, Units::Pixels(0.0));
+    world.set_layout_type(container, LayoutType::Column);
+
+    let child1 = world.add(Some(container));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(container));
+    world.set_height(child2, Units::Pixels(50.0));
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(container), 500.0);
+
+    world.set_height(child1, Units::Pixels(800.0));
+    world.mark_dirty(child1);
+
+    // The root is not recalculated, so the change to the sibling is not picked up
+    world.set_height(sibling, Units::Pixels(300.0));
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(container), 500.0);
+    assert_eq!(world.cache.height(child1), 800.0);
+    assert_eq!(world.cache.posy(child2), 800.0);
+
+    assert_eq!(world.cache.posy(sibling), 500.0);
+    assert_eq!(world.cache.height(sibling), 100.0);
+}
+
+/// Test of an auto sized container which is marked as a layout boundary by the user
+#[test]
+fn boundary_marked() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let container = world.add(Some(root));
+    world.set_height(container, Units::Auto);
+    world.set_layout_type(container, LayoutType::Column);
+    world.set_layout_boundary(container, true);
+
+    let child = world.add(Some(container));
+    world.set_height(child, Units::Pixels(50.0));
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(container), 50.0);
+
+    world.set_height(child, Units::Pixels(150.0));
+    world.mark_dirty(child);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    // The container keeps the size it had, as the change is not propagated past it
+    assert_eq!(world.cache.height(child), 150.0);
+    assert_eq!(world.cache.height(container), 50.0);
+    assert_eq!(world.cache.posy(sibling), 50.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(container), 150.0);
+    assert_eq!(world.cache.posy(sibling), 150.0);
+}
+
+/// Test of a percentage container with an auto min size, which grows with its children and so is not a layout boundary
+#[test]
+fn boundary_auto_min_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let container = world.add(Some(root));
+    world.set_height(container, Units::Percentage(50.0));
+    world.set_layout_type(container, LayoutType::Column);
+
+    let child = world.add(Some(container));
+    world.set_height(child, Units::Pixels(100.0));
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(container), 300.0);
+    assert_eq!(world.cache.posy(sibling), 300.0);
+
+    world.set_height(child, Units::Pixels(400.0));
+    world.mark_dirty(child);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(container), 400.0);
+    assert_eq!(world.cache.posy(sibling), 400.0);
+}
+
+/// Test of a grid with a fixed size which is detected as a layout boundary
+#[test]
+fn boundary_grid() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(300.0));
+    world.set_height(grid, Units::Pixels(200.0));
+    world.set_min_width(grid, Units::Pixels(0.0));
+    world.set_min_height(grid, Units::Pixels(0.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Auto, Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Auto, Units::Stretch(1.0)]);
+
+    let child1 = world.add(Some(grid));
+    world.set_width(child1, Units::Pixels(50.0));
+    world.set_height(child1, Units::Pixels(50.0));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    let child2 = world.add(Some(grid));
+    world.set_row(child2, 1, 1);
+    world.set_col(child2, 1, 1);
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child2), 50.0);
+    assert_eq!(world.cache.posy(child2), 50.0);
+
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(80.0));
+    world.mark_dirty(child1);
+
+    // The root is not recalculated, so the change to the sibling is not picked up
+    world.set_height(sibling, Units::Pixels(300.0));
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.posy(child2), 80.0);
+    assert_eq!(world.cache.width(child2), 200.0);
+    assert_eq!(world.cache.height(child2), 120.0);
+
+    assert_eq!(world.cache.posy(sibling), 200.0);
+    assert_eq!(world.cache.height(sibling), 100.0);
+}
+
+/// Test of a wrapping row with a fixed size which is detected as a layout boundary
+#[test]
+fn boundary_wrap() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(250.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_min_width(row, Units::Pixels(0.0));
+    world.set_min_height(row, Units::Pixels(0.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(30.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(30.0));
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.posy(child2), 0.0);
+
+    world.set_width(child1, Units::Pixels(200.0));
+    world.mark_dirty(child1);
+
+    // The root is not recalculated, so the change to the sibling is not picked up
+    world.set_height(sibling, Units::Pixels(300.0));
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    // The second child no longer fits on the first line
+    assert_eq!(world.cache.posx(child2), 0.0);
+    assert_eq!(world.cache.posy(child2), 30.0);
+
+    assert_eq!(world.cache.posy(sibling), 200.0);
+    assert_eq!(world.cache.height(sibling), 100.0);
+}
+
+/// Test of a layout boundary placed in a cell of an auto sized grid, which is laid out in the same way as by a full
+/// layout
+#[test]
+fn boundary_in_grid() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Auto);
+    world.set_height(grid, Units::Auto);
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Auto, Units::Auto]);
+    world.set_grid_cols(grid, vec![Units::Auto, Units::Auto]);
+
+    let container = world.add(Some(grid));
+    world.set_width(container, Units::Pixels(200.0));
+    world.set_height(container, Units::Pixels(100.0));
+    world.set_min_width(container, Units::Pixels(0.0));
+    world.set_min_height(container, Units::Pixels(0.0));
+    world.set_top(container, Units::Percentage(10.0));
+    world.set_layout_type(container, LayoutType::Column);
+    world.set_row(container, 0, 1);
+    world.set_col(container, 0, 1);
+
+    let child1 = world.add(Some(container));
+    world.set_height(child1, Units::Pixels(30.0));
+
+    let child2 = world.add(Some(container));
+    world.set_height(child2, Units::Pixels(30.0));
+
+    let cell = world.add(Some(grid));
+    world.set_width(cell, Units::Pixels(50.0));
+    world.set_height(cell, Units::Pixels(50.0));
+    world.set_left(cell, Units::Percentage(10.0));
+    world.set_row(cell, 1, 1);
+    world.set_col(cell, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    world.set_height(child1, Units::Pixels(60.0));
+    world.mark_dirty(child1);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    let relayout = [grid, container, child1, child2, cell]
+        .iter()
+        .map(|&node| {
+            (
+                world.cache.posx(node),
+                world.cache.posy(node),
+                world.cache.width(node),
+                world.cache.height(node),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(world.cache.posy(child2), relayout[1].1 + 60.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    let full_layout = [grid, container, child1, child2, cell]
+        .iter()
+        .map(|&node| {
+            (
+                world.cache.posx(node),
+                world.cache.posy(node),
+                world.cache.width(node),
+                world.cache.height(node),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(relayout, full_layout);
+}
@@ -0,0 +1,95 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of space and size which combine pixels and a percentage of the parent
+#[test]
+fn calc_units_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Calc(Calc::new(-40.0, 100.0)));
+    world.set_height(child1, Units::Calc(Calc::new(8.0, 50.0)));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(40.0));
+    world.set_top(child2, Units::Calc(Calc::new(10.0, 10.0)));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 560.0);
+    assert_eq!(world.cache.height(child1), 108.0);
+
+    assert_eq!(world.cache.posx(child2), 560.0);
+    assert_eq!(world.cache.posy(child2), 30.0);
+}
+
+/// Test of a sum of pixels and a percentage which is clamped by its minimum and maximum
+#[test]
+fn calc_units_min_max() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(600.0));
+    world.set_height(column, Units::Pixels(400.0));
+
+    let child1 = world.add(Some(column));
+    world.set_width(child1, Units::Calc(Calc::new(0.0, 100.0).max(300.0)));
+    world.set_height(child1, Units::Calc(Calc::new(-500.0, 100.0).min(50.0)));
+
+    // A sum can be used as the maximum size of a node
+    let child2 = world.add(Some(column));
+    world.set_width(child2, Units::Stretch(1.0));
+    world.set_max_width(child2, Units::Calc(Calc::new(-100.0, 50.0)));
+    world.set_height(child2, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 300.0);
+    assert_eq!(world.cache.height(child1), 50.0);
+
+    assert_eq!(world.cache.width(child2), 200.0);
+}
+
+/// Test of grid tracks which combine pixels and a percentage of the grid
+#[test]
+fn calc_units_grid_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Calc(Calc::new(-20.0, 50.0)), Units::Stretch(1.0)]);
+
+    let child1 = world.add(Some(grid));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    let child2 = world.add(Some(grid));
+    world.set_row(child2, 0, 1);
+    world.set_col(child2, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 280.0);
+    assert_eq!(world.cache.posx(child2), 280.0);
+    assert_eq!(world.cache.width(child2), 320.0);
+}
@@ -0,0 +1,260 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of the child with the lowest collapse priority which is hidden when the children overflow a row
+#[test]
+fn collapse_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_min_width(row, Units::Pixels(0.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_collapse(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(50.0));
+    world.set_collapse_priority(child2, 2);
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(50.0));
+    world.set_collapse_priority(child3, 1);
+
+    let child4 = world.add(Some(row));
+    world.set_width(child4, Units::Pixels(100.0));
+    world.set_height(child4, Units::Pixels(50.0));
+    world.set_collapse_priority(child4, 3);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    // The visibility of a collapsed child is left to the application
+    assert!(world.cache.visible(child3));
+    assert!(world.cache.collapsed(child3));
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.posx(child4), 200.0);
+
+    assert!(!world.cache.collapsed(child1));
+    assert!(!world.cache.collapsed(child2));
+    assert!(!world.cache.collapsed(child4));
+}
+
+/// Test of a collapsed child which is shown again once the row is wide enough
+#[test]
+fn collapse_row_relayout() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_min_width(row, Units::Pixels(0.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_collapse(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(50.0));
+    world.set_collapse_priority(child2, 2);
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(50.0));
+    world.set_collapse_priority(child3, 1);
+
+    let child4 = world.add(Some(row));
+    world.set_width(child4, Units::Pixels(100.0));
+    world.set_height(child4, Units::Pixels(50.0));
+    world.set_collapse_priority(child4, 3);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(world.cache.collapsed(child3));
+
+    world.set_width(row, Units::Pixels(400.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(world.cache.visible(child3));
+    assert!(!world.cache.collapsed(child3));
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.posx(child3), 200.0);
+    assert_eq!(world.cache.posx(child4), 300.0);
+}
+
+/// Test of children with the same collapse priority in a column, where the last child collapses first
+#[test]
+fn collapse_column_same_priority() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(100.0));
+    world.set_height(column, Units::Pixels(200.0));
+    world.set_min_height(column, Units::Pixels(0.0));
+    world.set_layout_type(column, LayoutType::Column);
+    world.set_layout_collapse(column, true);
+    world.set_child_top(column, Units::Pixels(10.0));
+    world.set_child_bottom(column, Units::Pixels(10.0));
+
+    let mut children = Vec::new();
+    for _ in 0..3 {
+        let child = world.add(Some(column));
+        world.set_height(child, Units::Pixels(80.0));
+        world.set_collapse_priority(child, 0);
+        children.push(child);
+    }
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(!world.cache.collapsed(children[0]));
+    assert!(!world.cache.collapsed(children[1]));
+    assert!(world.cache.collapsed(children[2]));
+
+    // The child space at the bottom of the column moves to the new last child
+    assert_eq!(world.cache.posy(children[0]), 10.0);
+    assert_eq!(world.cache.posy(children[1]), 90.0);
+    assert_eq!(world.cache.height(children[1]), 80.0);
+}
+
+/// Test of a collapsing row with the default min width, which collapses its children rather than growing to fit them
+#[test]
+fn collapse_row_default_min_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_collapse(row, true);
+
+    let mut children = Vec::new();
+    for priority in 0..4 {
+        let child = world.add(Some(row));
+        world.set_width(child, Units::Pixels(100.0));
+        world.set_height(child, Units::Pixels(50.0));
+        world.set_collapse_priority(child, priority);
+        children.push(child);
+    }
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(row), 300.0);
+
+    assert!(world.cache.collapsed(children[0]));
+    assert!(!world.cache.collapsed(children[1]));
+    assert_eq!(world.cache.posx(children[1]), 0.0);
+    assert_eq!(world.cache.posx(children[3]), 200.0);
+}
+
+/// Test of children with a percentage width, which take up their share of the row when it is decided what collapses
+#[test]
+fn collapse_row_percentage() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_min_width(row, Units::Pixels(0.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_collapse(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Percentage(50.0));
+    world.set_height(child1, Units::Pixels(50.0));
+    world.set_collapse_priority(child1, 1);
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Percentage(50.0));
+    world.set_height(child2, Units::Pixels(50.0));
+    world.set_collapse_priority(child2, 1);
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(!world.cache.collapsed(child1));
+    assert!(world.cache.collapsed(child2));
+    assert!(!world.cache.collapsed(child3));
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.width(child1), 150.0);
+    assert_eq!(world.cache.posx(child3), 150.0);
+}
+
+/// Test of a collapsed child which the application has hidden, which is not made visible by the next layout
+#[test]
+fn collapse_row_hidden() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(200.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_min_width(row, Units::Pixels(0.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_collapse(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(150.0));
+    world.set_height(child2, Units::Pixels(50.0));
+    world.set_collapse_priority(child2, 0);
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(50.0));
+    world.set_height(child3, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(world.cache.collapsed(child2));
+    assert_eq!(world.cache.posx(child3), 100.0);
+
+    world.cache.set_visible(child2, false);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(!world.cache.visible(child2));
+    assert!(!world.cache.collapsed(child2));
+    assert_eq!(world.cache.posx(child3), 100.0);
+}
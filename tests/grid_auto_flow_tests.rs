@@ -0,0 +1,188 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+// Adds a 300px by 150px grid with 3 columns of 100px and 3 rows of 50px to the root node
+fn add_grid(world: &mut World, auto_flow: Option<GridAutoFlow>) -> Entity {
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(300.0));
+    world.set_height(grid, Units::Pixels(150.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Pixels(50.0); 3]);
+    world.set_grid_cols(grid, vec![Units::Pixels(100.0); 3]);
+
+    if let Some(auto_flow) = auto_flow {
+        world.set_grid_auto_flow(grid, auto_flow);
+    }
+
+    grid
+}
+
+/// Test of children without an index in a grid without an auto flow
+#[test]
+fn grid_no_auto_flow() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world, None);
+
+    let child1 = world.add(Some(grid));
+    let child2 = world.add(Some(grid));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.posy(child1), 0.0);
+    assert_eq!(world.cache.posx(child2), 0.0);
+    assert_eq!(world.cache.posy(child2), 0.0);
+}
+
+/// Test of children placed by a row auto flow
+#[test]
+fn grid_auto_flow_row() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world, Some(GridAutoFlow::Row));
+
+    let children = (0..5).map(|_| world.add(Some(grid))).collect::<Vec<_>>();
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    let positions = children
+        .iter()
+        .map(|&child| (world.cache.posx(child), world.cache.posy(child)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(positions, vec![(0.0, 0.0), (100.0, 0.0), (200.0, 0.0), (0.0, 50.0), (100.0, 50.0)]);
+    assert_eq!(world.cache.width(children[0]), 100.0);
+    assert_eq!(world.cache.height(children[0]), 50.0);
+}
+
+/// Test of children placed by a column auto flow
+#[test]
+fn grid_auto_flow_column() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world, Some(GridAutoFlow::Column));
+
+    let children = (0..4).map(|_| world.add(Some(grid))).collect::<Vec<_>>();
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    let positions = children
+        .iter()
+        .map(|&child| (world.cache.posx(child), world.cache.posy(child)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(positions, vec![(0.0, 0.0), (0.0, 50.0), (0.0, 100.0), (100.0, 0.0)]);
+}
+
+/// Test of auto placed children skipping cells taken by explicitly placed children and respecting their span
+#[test]
+fn grid_auto_flow_row_span() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world, Some(GridAutoFlow::Row));
+
+    let explicit = world.add(Some(grid));
+    world.set_row(explicit, 0, 1);
+    world.set_col(explicit, 1, 1);
+
+    let wide = world.add(Some(grid));
+    world.set_col_span(wide, 2);
+
+    let child = world.add(Some(grid));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(wide), 0.0);
+    assert_eq!(world.cache.posy(wide), 50.0);
+    assert_eq!(world.cache.width(wide), 200.0);
+
+    assert_eq!(world.cache.posx(child), 200.0);
+    assert_eq!(world.cache.posy(child), 50.0);
+}
+
+/// Test of a dense row auto flow filling the gaps left by earlier children
+#[test]
+fn grid_auto_flow_row_dense() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world, Some(GridAutoFlow::RowDense));
+
+    let explicit = world.add(Some(grid));
+    world.set_row(explicit, 0, 1);
+    world.set_col(explicit, 1, 1);
+
+    let wide = world.add(Some(grid));
+    world.set_col_span(wide, 2);
+
+    let child1 = world.add(Some(grid));
+    let child2 = world.add(Some(grid));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(wide), 0.0);
+    assert_eq!(world.cache.posy(wide), 50.0);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.posy(child1), 0.0);
+
+    assert_eq!(world.cache.posx(child2), 200.0);
+    assert_eq!(world.cache.posy(child2), 0.0);
+}
+
+/// Test of auto placed children with only a row index or only a column index
+#[test]
+fn grid_auto_flow_partial_index() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world, Some(GridAutoFlow::Row));
+
+    let explicit = world.add(Some(grid));
+    world.set_row(explicit, 2, 1);
+    world.set_col(explicit, 0, 1);
+
+    let in_row = world.add(Some(grid));
+    world.set_row(in_row, 2, 1);
+
+    let in_col = world.add(Some(grid));
+    world.set_col(in_col, 2, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(in_row), 100.0);
+    assert_eq!(world.cache.posy(in_row), 100.0);
+
+    assert_eq!(world.cache.posx(in_col), 200.0);
+    assert_eq!(world.cache.posy(in_col), 0.0);
+}
+
+/// Test of an auto placed child with only a row index in a row which is already full
+#[test]
+fn grid_auto_flow_full_row() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world, Some(GridAutoFlow::Row));
+
+    let children = (0..3)
+        .map(|col| {
+            let child = world.add(Some(grid));
+            world.set_row(child, 1, 1);
+            world.set_col(child, col, 1);
+            child
+        })
+        .collect::<Vec<_>>();
+
+    let in_row = world.add(Some(grid));
+    world.set_row(in_row, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    // The child is placed in an implicit column after the explicit ones rather than over the first child
+    assert_eq!(world.cache.posx(children[0]), 0.0);
+    assert_eq!(world.cache.posx(in_row), 300.0);
+    assert_eq!(world.cache.posy(in_row), 50.0);
+}
@@ -0,0 +1,136 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of auto width and height on a grid with pixel tracks and no children
+#[test]
+fn grid_auto_size_pixel_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Auto);
+    world.set_height(grid, Units::Auto);
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Pixels(50.0), Units::Pixels(100.0)]);
+    world
+        .set_grid_cols(grid, vec![Units::Pixels(100.0), Units::Pixels(200.0), Units::Pixels(50.0)]);
+    world.set_row_between(grid, Units::Pixels(10.0));
+    world.set_col_between(grid, Units::Pixels(20.0));
+    world.set_child_space(grid, Units::Pixels(5.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(grid), 400.0);
+    assert_eq!(world.cache.height(grid), 170.0);
+}
+
+/// Test of auto width and height on a grid with stretch tracks sized by their children
+#[test]
+fn grid_auto_size_stretch_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Auto);
+    world.set_height(grid, Units::Auto);
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0), Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Stretch(1.0), Units::Pixels(100.0)]);
+
+    let child1 = world.add(Some(grid));
+    world.set_width(child1, Units::Pixels(150.0));
+    world.set_height(child1, Units::Pixels(40.0));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    let child2 = world.add(Some(grid));
+    world.set_width(child2, Units::Pixels(80.0));
+    world.set_height(child2, Units::Pixels(60.0));
+    world.set_row(child2, 1, 1);
+    world.set_col(child2, 0, 1);
+
+    // A child wider than a pixel track does not change the size of the track
+    let child3 = world.add(Some(grid));
+    world.set_width(child3, Units::Pixels(300.0));
+    world.set_height(child3, Units::Pixels(20.0));
+    world.set_row(child3, 1, 1);
+    world.set_col(child3, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(grid), 250.0);
+    assert_eq!(world.cache.height(grid), 100.0);
+}
+
+/// Test of a child spanning several tracks of an auto sized grid
+#[test]
+fn grid_auto_size_spanning_child() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Auto);
+    world.set_height(grid, Units::Auto);
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Stretch(1.0), Units::Stretch(1.0), Units::Pixels(50.0)]);
+    world.set_col_between(grid, Units::Pixels(10.0));
+
+    let child1 = world.add(Some(grid));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(30.0));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    // The missing 100px are shared between the two stretch columns
+    let child2 = world.add(Some(grid));
+    world.set_width(child2, Units::Pixels(270.0));
+    world.set_height(child2, Units::Pixels(30.0));
+    world.set_row(child2, 0, 1);
+    world.set_col(child2, 0, 3);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(grid), 270.0);
+    assert_eq!(world.cache.height(grid), 30.0);
+}
+
+/// Test of an auto sized grid nested within an auto sized grid
+#[test]
+fn grid_auto_size_nested_grid() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let outer = world.add(Some(root));
+    world.set_width(outer, Units::Auto);
+    world.set_height(outer, Units::Auto);
+    world.set_layout_type(outer, LayoutType::Grid);
+    world.set_grid_rows(outer, vec![Units::Stretch(1.0)]);
+    world.set_grid_cols(outer, vec![Units::Pixels(20.0), Units::Stretch(1.0)]);
+
+    let inner = world.add(Some(outer));
+    world.set_width(inner, Units::Auto);
+    world.set_height(inner, Units::Auto);
+    world.set_layout_type(inner, LayoutType::Grid);
+    world.set_grid_rows(inner, vec![Units::Pixels(30.0), Units::Pixels(30.0)]);
+    world.set_grid_cols(inner, vec![Units::Pixels(40.0)]);
+    world.set_row(inner, 0, 1);
+    world.set_col(inner, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(outer), 60.0);
+    assert_eq!(world.cache.height(outer), 60.0);
+}
@@ -0,0 +1,112 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of grid tracks which are positioned and sized within the border of the grid
+#[test]
+fn grid_border_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0), Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Percentage(50.0), Units::Stretch(1.0)]);
+    world.set_border(grid, Units::Pixels(10.0));
+
+    let child1 = world.add(Some(grid));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    let child2 = world.add(Some(grid));
+    world.set_row(child2, 1, 1);
+    world.set_col(child2, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 10.0);
+    assert_eq!(world.cache.posy(child1), 10.0);
+    assert_eq!(world.cache.width(child1), 290.0);
+    assert_eq!(world.cache.height(child1), 190.0);
+
+    assert_eq!(world.cache.posx(child2), 300.0);
+    assert_eq!(world.cache.posy(child2), 200.0);
+    assert_eq!(world.cache.width(child2), 290.0);
+    assert_eq!(world.cache.height(child2), 190.0);
+}
+
+/// Test of auto width and height on a grid with a border
+#[test]
+fn grid_border_auto_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Auto);
+    world.set_height(grid, Units::Auto);
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Pixels(50.0)]);
+    world.set_grid_cols(grid, vec![Units::Pixels(100.0), Units::Pixels(100.0)]);
+    world.set_border(grid, Units::Pixels(5.0));
+
+    let child = world.add(Some(grid));
+    world.set_row(child, 0, 1);
+    world.set_col(child, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(grid), 210.0);
+    assert_eq!(world.cache.height(grid), 60.0);
+
+    assert_eq!(world.cache.posx(child), 105.0);
+    assert_eq!(world.cache.posy(child), 5.0);
+    assert_eq!(world.cache.width(child), 100.0);
+    assert_eq!(world.cache.height(child), 50.0);
+}
+
+/// Test of a self-directed child of a grid which ignores the grid tracks and auto flow
+#[test]
+fn grid_self_directed_child() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0), Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Stretch(1.0), Units::Stretch(1.0)]);
+    world.set_grid_auto_flow(grid, GridAutoFlow::Row);
+    world.set_border(grid, Units::Pixels(10.0));
+
+    let overlay = world.add(Some(grid));
+    world.set_position_type(overlay, PositionType::SelfDirected);
+    world.set_left(overlay, Units::Pixels(20.0));
+    world.set_top(overlay, Units::Stretch(1.0));
+    world.set_width(overlay, Units::Pixels(100.0));
+    world.set_height(overlay, Units::Pixels(50.0));
+
+    let child = world.add(Some(grid));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(overlay), 30.0);
+    assert_eq!(world.cache.posy(overlay), 340.0);
+    assert_eq!(world.cache.width(overlay), 100.0);
+    assert_eq!(world.cache.height(overlay), 50.0);
+
+    assert_eq!(world.cache.posx(child), 10.0);
+    assert_eq!(world.cache.posy(child), 10.0);
+    assert_eq!(world.cache.width(child), 290.0);
+    assert_eq!(world.cache.height(child), 190.0);
+}
@@ -0,0 +1,97 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of a pixel sized child centered within its grid cell with stretch space
+#[test]
+fn grid_cell_stretch_space_center() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Stretch(1.0), Units::Stretch(1.0)]);
+
+    let button = world.add(Some(grid));
+    world.set_width(button, Units::Pixels(100.0));
+    world.set_height(button, Units::Pixels(40.0));
+    world.set_left(button, Units::Stretch(1.0));
+    world.set_right(button, Units::Stretch(1.0));
+    world.set_top(button, Units::Stretch(1.0));
+    world.set_bottom(button, Units::Stretch(1.0));
+    world.set_row(button, 0, 1);
+    world.set_col(button, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(button), 400.0);
+    assert_eq!(world.cache.posy(button), 180.0);
+    assert_eq!(world.cache.width(button), 100.0);
+    assert_eq!(world.cache.height(button), 40.0);
+}
+
+/// Test of percentage size and pixel space of a child within its grid cell
+#[test]
+fn grid_cell_percentage_size_pixel_space() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Pixels(100.0), Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Pixels(200.0), Units::Stretch(1.0)]);
+
+    let child = world.add(Some(grid));
+    world.set_width(child, Units::Percentage(50.0));
+    world.set_height(child, Units::Stretch(1.0));
+    world.set_left(child, Units::Pixels(10.0));
+    world.set_top(child, Units::Pixels(20.0));
+    world.set_bottom(child, Units::Pixels(30.0));
+    world.set_row(child, 1, 1);
+    world.set_col(child, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child), 210.0);
+    assert_eq!(world.cache.posy(child), 120.0);
+    assert_eq!(world.cache.width(child), 200.0);
+    assert_eq!(world.cache.height(child), 250.0);
+}
+
+/// Test of min and max size constraints on a stretch child within its grid cell
+#[test]
+fn grid_cell_min_max_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Pixels(50.0)]);
+    world.set_grid_cols(grid, vec![Units::Stretch(1.0)]);
+
+    let child = world.add(Some(grid));
+    world.set_max_width(child, Units::Pixels(200.0));
+    world.set_min_height(child, Units::Pixels(80.0));
+    world.set_left(child, Units::Stretch(1.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child), 400.0);
+    assert_eq!(world.cache.width(child), 200.0);
+    assert_eq!(world.cache.height(child), 80.0);
+}
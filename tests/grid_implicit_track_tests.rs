@@ -0,0 +1,143 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+// Adds a 300px by 150px grid with 2 columns of 100px and 2 rows of 50px to the root node
+fn add_grid(world: &mut World) -> Entity {
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(300.0));
+    world.set_height(grid, Units::Pixels(150.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Pixels(50.0); 2]);
+    world.set_grid_cols(grid, vec![Units::Pixels(100.0); 2]);
+
+    grid
+}
+
+/// Test of a child placed after the last row, which adds implicit auto rows
+#[test]
+fn grid_implicit_auto_rows() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world);
+
+    let child = world.add(Some(grid));
+    world.set_height(child, Units::Pixels(30.0));
+    world.set_row(child, 3, 1);
+    world.set_col(child, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child), 100.0);
+    assert_eq!(world.cache.posy(child), 100.0);
+    assert_eq!(world.cache.width(child), 100.0);
+    assert_eq!(world.cache.height(child), 30.0);
+}
+
+/// Test of a child spanning past the last column, which adds implicit pixel columns
+#[test]
+fn grid_implicit_pixel_cols() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world);
+    world.set_grid_auto_cols(grid, Units::Pixels(40.0));
+
+    let child = world.add(Some(grid));
+    world.set_row(child, 0, 1);
+    world.set_col(child, 1, 3);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child), 100.0);
+    assert_eq!(world.cache.width(child), 180.0);
+}
+
+/// Test of auto placed children overflowing into implicit rows
+#[test]
+fn grid_implicit_rows_auto_flow() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world);
+    world.set_grid_auto_flow(grid, GridAutoFlow::Row);
+    world.set_grid_auto_rows(grid, Units::Pixels(25.0));
+
+    let children = (0..5).map(|_| world.add(Some(grid))).collect::<Vec<_>>();
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(children[4]), 0.0);
+    assert_eq!(world.cache.posy(children[4]), 100.0);
+    assert_eq!(world.cache.height(children[4]), 25.0);
+}
+
+/// Test of negative indices counting back from the last row and column
+#[test]
+fn grid_negative_index() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world);
+
+    let last = world.add(Some(grid));
+    world.set_row(last, -1, 1);
+    world.set_col(last, -1, 1);
+
+    let clamped = world.add(Some(grid));
+    world.set_row(clamped, -5, 1);
+    world.set_col(clamped, -2, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(last), 100.0);
+    assert_eq!(world.cache.posy(last), 50.0);
+
+    assert_eq!(world.cache.posx(clamped), 0.0);
+    assert_eq!(world.cache.posy(clamped), 0.0);
+}
+
+/// Test of a grid without any explicit tracks
+#[test]
+fn grid_no_explicit_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(300.0));
+    world.set_height(grid, Units::Pixels(150.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_auto_rows(grid, Units::Stretch(1.0));
+    world.set_grid_auto_cols(grid, Units::Stretch(1.0));
+
+    let child = world.add(Some(grid));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child), 300.0);
+    assert_eq!(world.cache.height(child), 150.0);
+}
+
+/// Test of a child placed far past the last row, which only adds implicit rows up to the largest number of tracks
+#[test]
+fn grid_implicit_track_limit() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world);
+    world.set_grid_auto_rows(grid, Units::Pixels(1.0));
+
+    let child = world.add(Some(grid));
+    world.set_row(child, 1_000_000, 1);
+    world.set_col(child, 0, 1_000_000);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    // The child is moved back into the last of the 1000 rows, and spans the 1000 columns
+    assert_eq!(world.cache.grid_row_index(child), 999);
+    assert_eq!(world.cache.posy(child), 1097.0);
+    assert_eq!(world.cache.height(child), 1.0);
+    assert_eq!(world.cache.posx(child), 0.0);
+}
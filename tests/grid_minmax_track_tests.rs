@@ -0,0 +1,190 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of a stretch grid column with a minimum width which gives up the space it takes to the other columns
+#[test]
+fn grid_stretch_track_min() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(500.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0)]);
+    world.set_grid_cols(
+        grid,
+        vec![
+            GridTrack::from(Units::Stretch(1.0)).min(Units::Pixels(200.0)),
+            GridTrack::from(Units::Stretch(2.0)),
+            GridTrack::from(Units::Stretch(1.0)),
+        ],
+    );
+
+    let sidebar = world.add(Some(grid));
+    world.set_row(sidebar, 0, 1);
+    world.set_col(sidebar, 0, 1);
+
+    let content = world.add(Some(grid));
+    world.set_row(content, 0, 1);
+    world.set_col(content, 1, 1);
+
+    let panel = world.add(Some(grid));
+    world.set_row(panel, 0, 1);
+    world.set_col(panel, 2, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(sidebar), 0.0);
+    assert_eq!(world.cache.width(sidebar), 200.0);
+
+    assert_eq!(world.cache.posx(content), 200.0);
+    assert_eq!(world.cache.width(content), 200.0);
+
+    assert_eq!(world.cache.posx(panel), 400.0);
+    assert_eq!(world.cache.width(panel), 100.0);
+}
+
+/// Test of a stretch grid column with a maximum width which gives up the space it would take to the other columns
+#[test]
+fn grid_stretch_track_max() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0)]);
+    world.set_grid_cols(
+        grid,
+        vec![
+            GridTrack::from(Units::Stretch(1.0)).max(Units::Percentage(10.0)),
+            GridTrack::from(Units::Stretch(1.0)),
+            GridTrack::from(Units::Stretch(1.0)),
+        ],
+    );
+    world.set_col_between(grid, Units::Pixels(10.0));
+
+    let child1 = world.add(Some(grid));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    let child2 = world.add(Some(grid));
+    world.set_row(child2, 0, 1);
+    world.set_col(child2, 1, 1);
+
+    let child3 = world.add(Some(grid));
+    world.set_row(child3, 0, 1);
+    world.set_col(child3, 2, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 60.0);
+
+    assert_eq!(world.cache.posx(child2), 70.0);
+    assert_eq!(world.cache.width(child2), 260.0);
+
+    assert_eq!(world.cache.posx(child3), 340.0);
+    assert_eq!(world.cache.width(child3), 260.0);
+}
+
+/// Test of fixed and auto grid rows clamped by their minimum and maximum heights
+#[test]
+fn grid_fixed_and_auto_track_minmax() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(400.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(
+        grid,
+        vec![
+            GridTrack::minmax(Units::Pixels(50.0), Units::Percentage(5.0), Units::Auto),
+            GridTrack::from(Units::Auto).max(Units::Pixels(40.0)),
+            GridTrack::from(Units::Auto).min(Units::Pixels(30.0)),
+            GridTrack::from(Units::Stretch(1.0)),
+        ],
+    );
+    world.set_grid_cols(grid, vec![Units::Stretch(1.0)]);
+
+    let child1 = world.add(Some(grid));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    let child2 = world.add(Some(grid));
+    world.set_height(child2, Units::Pixels(60.0));
+    world.set_row(child2, 1, 1);
+    world.set_col(child2, 0, 1);
+
+    let child3 = world.add(Some(grid));
+    world.set_height(child3, Units::Pixels(10.0));
+    world.set_row(child3, 2, 1);
+    world.set_col(child3, 0, 1);
+
+    let child4 = world.add(Some(grid));
+    world.set_row(child4, 3, 1);
+    world.set_col(child4, 0, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posy(child1), 0.0);
+    assert_eq!(world.cache.height(child1), 50.0);
+
+    assert_eq!(world.cache.posy(child2), 50.0);
+    assert_eq!(world.cache.height(child2), 60.0);
+
+    assert_eq!(world.cache.posy(child3), 90.0);
+    assert_eq!(world.cache.height(child3), 10.0);
+
+    assert_eq!(world.cache.posy(child4), 120.0);
+    assert_eq!(world.cache.height(child4), 280.0);
+}
+
+/// Test of auto width on a grid with stretch columns clamped by pixel minimum and maximum widths
+#[test]
+fn grid_auto_size_track_minmax() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Auto);
+    world.set_height(grid, Units::Pixels(100.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0)]);
+    world.set_grid_cols(
+        grid,
+        vec![
+            GridTrack::from(Units::Stretch(1.0)).min(Units::Pixels(100.0)),
+            GridTrack::from(Units::Stretch(1.0)).max(Units::Pixels(50.0)),
+        ],
+    );
+
+    let child1 = world.add(Some(grid));
+    world.set_width(child1, Units::Pixels(40.0));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    let child2 = world.add(Some(grid));
+    world.set_width(child2, Units::Pixels(30.0));
+    world.set_row(child2, 0, 1);
+    world.set_col(child2, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(grid), 130.0);
+}
@@ -0,0 +1,131 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+// Adds a 600x400 grid with a header, sidebar, content, and footer area to the world
+fn add_grid(world: &mut World) -> Entity {
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Pixels(50.0), Units::Stretch(1.0), Units::Pixels(30.0)]);
+    world
+        .set_grid_cols(grid, vec![Units::Pixels(150.0), Units::Stretch(1.0), Units::Pixels(100.0)]);
+    world.set_grid_template_areas(
+        grid,
+        vec![
+            vec!["header", "header", "header"],
+            vec!["sidebar", "content", "."],
+            vec!["footer", "footer", "footer"],
+        ],
+    );
+
+    grid
+}
+
+/// Test of children placed by the name of a grid area
+#[test]
+fn grid_named_areas() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world);
+
+    let header = world.add(Some(grid));
+    world.set_grid_area(header, "header");
+
+    let sidebar = world.add(Some(grid));
+    world.set_grid_area(sidebar, "sidebar");
+
+    let content = world.add(Some(grid));
+    world.set_grid_area(content, "content");
+
+    let footer = world.add(Some(grid));
+    world.set_grid_area(footer, "footer");
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(header), 0.0);
+    assert_eq!(world.cache.posy(header), 0.0);
+    assert_eq!(world.cache.width(header), 600.0);
+    assert_eq!(world.cache.height(header), 50.0);
+
+    assert_eq!(world.cache.posx(sidebar), 0.0);
+    assert_eq!(world.cache.posy(sidebar), 50.0);
+    assert_eq!(world.cache.width(sidebar), 150.0);
+    assert_eq!(world.cache.height(sidebar), 320.0);
+
+    assert_eq!(world.cache.posx(content), 150.0);
+    assert_eq!(world.cache.posy(content), 50.0);
+    assert_eq!(world.cache.width(content), 350.0);
+    assert_eq!(world.cache.height(content), 320.0);
+
+    assert_eq!(world.cache.posx(footer), 0.0);
+    assert_eq!(world.cache.posy(footer), 370.0);
+    assert_eq!(world.cache.width(footer), 600.0);
+    assert_eq!(world.cache.height(footer), 30.0);
+}
+
+/// Test of a grid area name which takes precedence over the row and column index of a child
+#[test]
+fn grid_named_area_overrides_index() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world);
+
+    let content = world.add(Some(grid));
+    world.set_grid_area(content, "content");
+    world.set_row(content, 2, 1);
+    world.set_col(content, 0, 3);
+
+    // An unknown area name falls back to the row and column index
+    let panel = world.add(Some(grid));
+    world.set_grid_area(panel, "panel");
+    world.set_row(panel, 1, 1);
+    world.set_col(panel, 2, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(content), 150.0);
+    assert_eq!(world.cache.posy(content), 50.0);
+    assert_eq!(world.cache.width(content), 350.0);
+    assert_eq!(world.cache.height(content), 320.0);
+
+    assert_eq!(world.cache.posx(panel), 500.0);
+    assert_eq!(world.cache.posy(panel), 50.0);
+    assert_eq!(world.cache.width(panel), 100.0);
+    assert_eq!(world.cache.height(panel), 320.0);
+}
+
+/// Test of children placed between named grid lines
+#[test]
+fn grid_named_lines() {
+    let mut world = World::default();
+
+    let grid = add_grid(&mut world);
+    world.set_grid_col_lines(grid, vec![("main-start", 1), ("main-end", 3)]);
+    world.set_grid_row_lines(grid, vec![("top", 0)]);
+
+    let toolbar = world.add(Some(grid));
+    world.set_row_lines(toolbar, "top", "header-end");
+    world.set_col_lines(toolbar, "main-start", "main-end");
+
+    // The lines at the edges of the named areas can be used as well
+    let panel = world.add(Some(grid));
+    world.set_row_lines(panel, "content-start", "footer-end");
+    world.set_col_lines(panel, "content-end", "main-end");
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(toolbar), 150.0);
+    assert_eq!(world.cache.posy(toolbar), 0.0);
+    assert_eq!(world.cache.width(toolbar), 450.0);
+    assert_eq!(world.cache.height(toolbar), 50.0);
+
+    assert_eq!(world.cache.posx(panel), 500.0);
+    assert_eq!(world.cache.posy(panel), 50.0);
+    assert_eq!(world.cache.width(panel), 100.0);
+    assert_eq!(world.cache.height(panel), 350.0);
+}
@@ -0,0 +1,134 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of percentage grid tracks which resolve against the size of the grid
+#[test]
+fn grid_percentage_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Percentage(50.0), Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Percentage(25.0), Units::Stretch(1.0)]);
+
+    let child1 = world.add(Some(grid));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    let child2 = world.add(Some(grid));
+    world.set_row(child2, 1, 1);
+    world.set_col(child2, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.posy(child1), 0.0);
+    assert_eq!(world.cache.width(child1), 150.0);
+    assert_eq!(world.cache.height(child1), 200.0);
+
+    assert_eq!(world.cache.posx(child2), 150.0);
+    assert_eq!(world.cache.posy(child2), 200.0);
+    assert_eq!(world.cache.width(child2), 450.0);
+    assert_eq!(world.cache.height(child2), 200.0);
+}
+
+/// Test of auto grid tracks which fit the largest child placed in them
+#[test]
+fn grid_auto_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Auto, Units::Auto]);
+    world.set_grid_cols(grid, vec![Units::Auto, Units::Stretch(1.0)]);
+    world.set_row_between(grid, Units::Pixels(10.0));
+
+    let label1 = world.add(Some(grid));
+    world.set_width(label1, Units::Pixels(120.0));
+    world.set_height(label1, Units::Pixels(30.0));
+    world.set_row(label1, 0, 1);
+    world.set_col(label1, 0, 1);
+
+    let label2 = world.add(Some(grid));
+    world.set_width(label2, Units::Pixels(80.0));
+    world.set_height(label2, Units::Pixels(50.0));
+    world.set_row(label2, 1, 1);
+    world.set_col(label2, 0, 1);
+
+    let field = world.add(Some(grid));
+    world.set_row(field, 0, 1);
+    world.set_col(field, 1, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(label1), 120.0);
+    assert_eq!(world.cache.height(label1), 30.0);
+
+    assert_eq!(world.cache.posy(label2), 40.0);
+    assert_eq!(world.cache.width(label2), 80.0);
+    assert_eq!(world.cache.height(label2), 50.0);
+
+    assert_eq!(world.cache.posx(field), 120.0);
+    assert_eq!(world.cache.width(field), 480.0);
+    assert_eq!(world.cache.height(field), 30.0);
+}
+
+/// Test of a child spanning several auto grid tracks
+#[test]
+fn grid_auto_tracks_spanning_child() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(600.0));
+    world.set_height(grid, Units::Pixels(400.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Auto, Units::Auto]);
+    world.set_grid_cols(grid, vec![Units::Auto, Units::Auto, Units::Stretch(1.0)]);
+
+    let child1 = world.add(Some(grid));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(20.0));
+    world.set_row(child1, 0, 1);
+    world.set_col(child1, 0, 1);
+
+    // The missing 200px are shared between the two auto columns
+    let child2 = world.add(Some(grid));
+    world.set_width(child2, Units::Pixels(300.0));
+    world.set_height(child2, Units::Pixels(20.0));
+    world.set_row(child2, 1, 1);
+    world.set_col(child2, 0, 2);
+
+    // A span which includes a stretch column does not grow the auto columns
+    let child3 = world.add(Some(grid));
+    world.set_width(child3, Units::Pixels(400.0));
+    world.set_height(child3, Units::Pixels(20.0));
+    world.set_row(child3, 0, 1);
+    world.set_col(child3, 1, 2);
+
+    let filler = world.add(Some(grid));
+    world.set_row(filler, 1, 1);
+    world.set_col(filler, 2, 1);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child2), 300.0);
+    assert_eq!(world.cache.posx(child3), 200.0);
+    assert_eq!(world.cache.posx(filler), 300.0);
+    assert_eq!(world.cache.width(filler), 300.0);
+}
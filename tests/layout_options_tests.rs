@@ -0,0 +1,127 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of the same tree laid out with and without rounding to whole pixels
+#[test]
+fn layout_options_rounding() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(100.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    let child2 = world.add(Some(row));
+    let child3 = world.add(Some(row));
+
+    let options = LayoutOptions::default().rounding(Rounding::Pixels);
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.width(child1), 33.0);
+    assert_eq!(world.cache.width(child2), 34.0);
+    assert_eq!(world.cache.width(child3), 33.0);
+
+    let options = LayoutOptions::default().rounding(Rounding::None);
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.width(child1), 100.0 / 3.0);
+    assert_eq!(world.cache.width(child2), 100.0 / 3.0);
+    assert_eq!(world.cache.width(child3), 100.0 / 3.0);
+}
+
+/// Test of rounding percentage space and size selected at runtime
+#[test]
+fn layout_options_rounding_percentage() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let child = world.add(Some(root));
+    world.set_left(child, Units::Percentage(6.25));
+    world.set_width(child, Units::Percentage(31.25));
+    world.set_height(child, Units::Pixels(50.0));
+
+    let options = LayoutOptions::default().rounding(Rounding::Pixels);
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.posx(child), 63.0);
+    assert_eq!(world.cache.width(child), 313.0);
+
+    let options = LayoutOptions::default().rounding(Rounding::None);
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.posx(child), 62.5);
+    assert_eq!(world.cache.width(child), 312.5);
+}
+
+/// Test of the size of nodes without a width or height set by the options
+#[test]
+fn layout_options_default_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let parent = world.add(Some(root));
+
+    let child = world.add(Some(parent));
+    world.set_width(child, Units::Pixels(100.0));
+    world.set_height(child, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(parent), 1000.0);
+    assert_eq!(world.cache.height(parent), 600.0);
+
+    // Nodes without a width or height fit their children instead of stretching
+    let options = LayoutOptions::default().default_size(Units::Auto);
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.width(parent), 100.0);
+    assert_eq!(world.cache.height(parent), 50.0);
+}
+
+/// Test of a wrapping row without a width, which takes its size from the options before it is split into lines
+#[test]
+fn layout_options_default_size_wrap() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let parent = world.add(Some(root));
+    world.set_width(parent, Units::Pixels(300.0));
+    world.set_height(parent, Units::Pixels(400.0));
+    world.set_min_width(parent, Units::Pixels(0.0));
+    world.set_layout_type(parent, LayoutType::Column);
+
+    let row = world.add(Some(parent));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+
+    let mut children = Vec::new();
+    for _ in 0..4 {
+        let child = world.add(Some(row));
+        world.set_width(child, Units::Pixels(100.0));
+        world.set_height(child, Units::Pixels(30.0));
+        children.push(child);
+    }
+
+    let options = LayoutOptions::default().default_size(Units::Stretch(1.0));
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.width(row), 300.0);
+    assert_eq!(world.cache.height(row), 60.0);
+    assert_eq!(world.cache.posx(children[3]), 0.0);
+    assert_eq!(world.cache.posy(children[3]), 30.0);
+}
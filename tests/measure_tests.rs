@@ -0,0 +1,147 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Test of the measured size of a text node which is stored in the cache with the width it was measured against
+#[test]
+fn measure_text() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    // The text wraps onto 20px lines rather than growing the node to fit it on a single line
+    let count = Rc::new(Cell::new(0));
+    let text = world.add(Some(root));
+    world.set_width(text, Units::Stretch(1.0));
+    world.set_min_width(text, Units::Pixels(0.0));
+    world.set_height(text, Units::Auto);
+    world.set_layout_type(text, LayoutType::Row);
+    world.set_content_width(text, 2500.0);
+    world.set_content_height(text, 20.0);
+    let measured = count.clone();
+    world.set_content_height_secondary(text, move |width| {
+        measured.set(measured.get() + 1);
+        (2500.0 / width).ceil() * 20.0
+    });
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(text), 60.0);
+    assert_eq!(
+        world.cache.measured_size(text, Direction::Y, false),
+        Some(MeasuredSize { available: Some(1000.0), size: 60.0 })
+    );
+
+    // A full layout reuses the measured size while the width stays the same
+    let layout_count = count.get();
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(count.get(), layout_count);
+    assert_eq!(world.cache.height(text), 60.0);
+
+    world.mark_dirty(text);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(count.get() > layout_count);
+    assert_eq!(world.cache.height(text), 60.0);
+}
+
+/// Test of a clean text node which is moved by a relayout without being measured again
+#[test]
+fn measure_reused() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let count = Rc::new(Cell::new(0));
+    let text = world.add(Some(root));
+    world.set_width(text, Units::Stretch(1.0));
+    world.set_min_width(text, Units::Pixels(0.0));
+    world.set_height(text, Units::Auto);
+    world.set_layout_type(text, LayoutType::Row);
+    world.set_content_width(text, 2500.0);
+    world.set_content_height(text, 20.0);
+    let measured = count.clone();
+    world.set_content_height_secondary(text, move |width| {
+        measured.set(measured.get() + 1);
+        (2500.0 / width).ceil() * 20.0
+    });
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    let layout_count = count.get();
+
+    world.set_height(sibling, Units::Pixels(200.0));
+    world.mark_dirty(sibling);
+
+    // The sibling is after the text so the root is laid out again, but the text is measured with the same width
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(count.get(), layout_count);
+    assert_eq!(world.cache.height(text), 60.0);
+    assert_eq!(world.cache.height(sibling), 200.0);
+
+    world.mark_dirty(text);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(count.get() > layout_count);
+}
+
+/// Test of a clean text node which is measured again when the width available to it changes
+#[test]
+fn measure_constraints_changed() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let count = Rc::new(Cell::new(0));
+    let text = world.add(Some(root));
+    world.set_width(text, Units::Stretch(1.0));
+    world.set_min_width(text, Units::Pixels(0.0));
+    world.set_height(text, Units::Auto);
+    world.set_layout_type(text, LayoutType::Row);
+    world.set_content_width(text, 2500.0);
+    world.set_content_height(text, 20.0);
+    let measured = count.clone();
+    world.set_content_height_secondary(text, move |width| {
+        measured.set(measured.get() + 1);
+        (2500.0 / width).ceil() * 20.0
+    });
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(text), 60.0);
+
+    let layout_count = count.get();
+
+    world.set_width(root, Units::Pixels(500.0));
+    world.cache.set_width(root, 500.0);
+    world.mark_dirty(root);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(count.get(), layout_count + 1);
+    assert_eq!(world.cache.width(text), 500.0);
+    assert_eq!(world.cache.height(text), 100.0);
+    assert_eq!(
+        world.cache.measured_size(text, Direction::Y, false),
+        Some(MeasuredSize { available: Some(500.0), size: 100.0 })
+    );
+}
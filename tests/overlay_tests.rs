@@ -0,0 +1,112 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of the children of an overlay which are positioned on top of each other
+#[test]
+fn overlay_children() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let overlay = world.add(Some(root));
+    world.set_width(overlay, Units::Pixels(400.0));
+    world.set_height(overlay, Units::Pixels(300.0));
+    world.set_layout_type(overlay, LayoutType::Overlay);
+    world.set_child_space(overlay, Units::Stretch(1.0));
+
+    let background = world.add(Some(overlay));
+    world.set_left(background, Units::Pixels(0.0));
+    world.set_right(background, Units::Pixels(0.0));
+    world.set_top(background, Units::Pixels(0.0));
+    world.set_bottom(background, Units::Pixels(0.0));
+
+    let dialog = world.add(Some(overlay));
+    world.set_width(dialog, Units::Pixels(100.0));
+    world.set_height(dialog, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(background), 0.0);
+    assert_eq!(world.cache.posy(background), 0.0);
+    assert_eq!(world.cache.width(background), 400.0);
+    assert_eq!(world.cache.height(background), 300.0);
+
+    assert_eq!(world.cache.posx(dialog), 150.0);
+    assert_eq!(world.cache.posy(dialog), 125.0);
+    assert_eq!(world.cache.width(dialog), 100.0);
+    assert_eq!(world.cache.height(dialog), 50.0);
+}
+
+/// Test of the children of an overlay with a border and child space in pixels
+#[test]
+fn overlay_border_and_child_space() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let overlay = world.add(Some(root));
+    world.set_width(overlay, Units::Pixels(400.0));
+    world.set_height(overlay, Units::Pixels(300.0));
+    world.set_layout_type(overlay, LayoutType::Overlay);
+    world.set_border(overlay, Units::Pixels(5.0));
+    world.set_child_space(overlay, Units::Pixels(10.0));
+
+    let child1 = world.add(Some(overlay));
+
+    let child2 = world.add(Some(overlay));
+    world.set_left(child2, Units::Stretch(1.0));
+    world.set_width(child2, Units::Pixels(50.0));
+    world.set_height(child2, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 15.0);
+    assert_eq!(world.cache.posy(child1), 15.0);
+    assert_eq!(world.cache.width(child1), 370.0);
+    assert_eq!(world.cache.height(child1), 270.0);
+
+    assert_eq!(world.cache.posx(child2), 335.0);
+    assert_eq!(world.cache.posy(child2), 15.0);
+}
+
+/// Test of auto width and height on an overlay which fits its largest children
+#[test]
+fn overlay_auto_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let overlay = world.add(Some(root));
+    world.set_width(overlay, Units::Auto);
+    world.set_height(overlay, Units::Auto);
+    world.set_layout_type(overlay, LayoutType::Overlay);
+
+    let child1 = world.add(Some(overlay));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(overlay));
+    world.set_left(child2, Units::Pixels(10.0));
+    world.set_width(child2, Units::Pixels(60.0));
+    world.set_height(child2, Units::Pixels(80.0));
+
+    // A self-directed child does not contribute to the size of the overlay
+    let child3 = world.add(Some(overlay));
+    world.set_position_type(child3, PositionType::SelfDirected);
+    world.set_width(child3, Units::Pixels(500.0));
+    world.set_height(child3, Units::Pixels(500.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(overlay), 100.0);
+    assert_eq!(world.cache.height(overlay), 80.0);
+
+    assert_eq!(world.cache.posx(child2), 10.0);
+    assert_eq!(world.cache.posy(child2), 0.0);
+}
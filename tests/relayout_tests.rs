@@ -0,0 +1,372 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of a sibling which moves when a dirty node grows
+#[test]
+fn relayout_sibling() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let child1 = world.add(Some(root));
+    world.set_height(child1, Units::Pixels(100.0));
+
+    let child2 = world.add(Some(root));
+    world.set_height(child2, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posy(child2), 100.0);
+
+    world.set_height(child1, Units::Pixels(200.0));
+    world.mark_dirty(child1);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(child1), 200.0);
+    assert_eq!(world.cache.posy(child2), 200.0);
+    assert_eq!(world.cache.height(child2), 100.0);
+}
+
+/// Test of an auto sized parent which grows with a dirty child and moves its own sibling
+#[test]
+fn relayout_auto_parent() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let container = world.add(Some(root));
+    world.set_height(container, Units::Auto);
+    world.set_layout_type(container, LayoutType::Column);
+
+    let child = world.add(Some(container));
+    world.set_height(child, Units::Pixels(50.0));
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(container), 50.0);
+    assert_eq!(world.cache.posy(sibling), 50.0);
+
+    world.set_height(child, Units::Pixels(150.0));
+    world.mark_dirty(child);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(child), 150.0);
+    assert_eq!(world.cache.height(container), 150.0);
+    assert_eq!(world.cache.posy(sibling), 150.0);
+}
+
+/// Test of a container with a fixed size which stops a change within it from reaching its ancestors
+#[test]
+fn relayout_fixed_size_boundary() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let container = world.add(Some(root));
+    world.set_width(container, Units::Pixels(200.0));
+    world.set_height(container, Units::Pixels(200.0));
+    world.set_min_width(container, Units::Pixels(0.0));
+    world.set_min_height(container, Units::Pixels(0.0));
+    world.set_layout_type(container, LayoutType::Column);
+
+    let child1 = world.add(Some(container));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(container));
+    world.set_height(child2, Units::Pixels(50.0));
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    world.set_height(child1, Units::Pixels(100.0));
+    world.mark_dirty(child1);
+
+    // The sibling is not marked and the root is not recalculated, so the change to the sibling is not picked up
+    world.set_height(sibling, Units::Pixels(300.0));
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(child1), 100.0);
+    assert_eq!(world.cache.posy(child2), 100.0);
+
+    assert_eq!(world.cache.posy(sibling), 200.0);
+    assert_eq!(world.cache.height(sibling), 100.0);
+}
+
+/// Test of an auto sized grid which is measured again when its parent is recalculated
+#[test]
+fn relayout_grid() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Auto);
+    world.set_height(grid, Units::Auto);
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Auto]);
+    world.set_grid_cols(grid, vec![Units::Auto]);
+
+    let child = world.add(Some(grid));
+    world.set_width(child, Units::Pixels(100.0));
+    world.set_height(child, Units::Pixels(30.0));
+    world.set_top(child, Units::Percentage(10.0));
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    let height = world.cache.height(grid);
+    let posy = world.cache.posy(child);
+
+    world.set_height(sibling, Units::Pixels(200.0));
+    world.mark_dirty(sibling);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(grid), height);
+    assert_eq!(world.cache.posy(child), posy);
+    assert_eq!(world.cache.posy(sibling), height);
+}
+
+/// Test of an auto sized wrapping row which is measured again when its parent is recalculated
+#[test]
+fn relayout_wrap() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(250.0));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(20.0));
+    world.set_top(child1, Units::Percentage(50.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(30.0));
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(row), 30.0);
+    assert_eq!(world.cache.posy(child1), 15.0);
+
+    world.set_height(sibling, Units::Pixels(200.0));
+    world.mark_dirty(sibling);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(row), 30.0);
+    assert_eq!(world.cache.posy(child1), 15.0);
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.posy(sibling), 30.0);
+}
+
+/// Test of an auto sized row aligned on baselines which is measured again when its parent is recalculated
+#[test]
+fn relayout_baseline() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let row = world.add(Some(root));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_align_baseline(row, true);
+
+    let label = world.add(Some(row));
+    world.set_width(label, Units::Pixels(80.0));
+    world.set_height(label, Units::Pixels(20.0));
+    world.set_top(label, Units::Percentage(50.0));
+    world.set_baseline(label, 15.0);
+
+    let button = world.add(Some(row));
+    world.set_width(button, Units::Pixels(100.0));
+    world.set_height(button, Units::Pixels(40.0));
+    world.set_baseline(button, 25.0);
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(row), 40.0);
+    assert_eq!(world.cache.posy(label), 20.0);
+
+    world.set_height(sibling, Units::Pixels(200.0));
+    world.mark_dirty(sibling);
+
+    relayout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.height(row), 40.0);
+    assert_eq!(world.cache.posy(label), 20.0);
+    assert_eq!(world.cache.posy(sibling), 40.0);
+}
+
+/// Test of a grid, a wrapping row and a collapsing row nested below the dirty nodes, which are laid out by repeated
+/// relayouts in the same way as by a full layout
+#[test]
+fn relayout_nested_repeated() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let grid_wrapper = world.add(Some(root));
+    world.set_height(grid_wrapper, Units::Auto);
+    world.set_layout_type(grid_wrapper, LayoutType::Column);
+
+    let grid = world.add(Some(grid_wrapper));
+    world.set_width(grid, Units::Auto);
+    world.set_height(grid, Units::Auto);
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Auto]);
+    world.set_grid_cols(grid, vec![Units::Auto, Units::Pixels(50.0)]);
+
+    let cell = world.add(Some(grid));
+    world.set_width(cell, Units::Pixels(30.0));
+    world.set_height(cell, Units::Pixels(20.0));
+    world.set_left(cell, Units::Percentage(50.0));
+    world.set_row(cell, 0, 1);
+    world.set_col(cell, 0, 1);
+
+    let wrap_wrapper = world.add(Some(root));
+    world.set_height(wrap_wrapper, Units::Auto);
+    world.set_layout_type(wrap_wrapper, LayoutType::Column);
+
+    let wrap_row = world.add(Some(wrap_wrapper));
+    world.set_width(wrap_row, Units::Pixels(250.0));
+    world.set_height(wrap_row, Units::Auto);
+    world.set_layout_type(wrap_row, LayoutType::Row);
+    world.set_layout_wrap(wrap_row, true);
+
+    let wrap_child1 = world.add(Some(wrap_row));
+    world.set_width(wrap_child1, Units::Pixels(100.0));
+    world.set_height(wrap_child1, Units::Pixels(20.0));
+    world.set_top(wrap_child1, Units::Percentage(50.0));
+
+    let wrap_child2 = world.add(Some(wrap_row));
+    world.set_width(wrap_child2, Units::Percentage(50.0));
+    world.set_height(wrap_child2, Units::Pixels(30.0));
+
+    let collapse_wrapper = world.add(Some(root));
+    world.set_width(collapse_wrapper, Units::Pixels(200.0));
+    world.set_height(collapse_wrapper, Units::Pixels(50.0));
+    world.set_layout_type(collapse_wrapper, LayoutType::Column);
+
+    let inner = world.add(Some(collapse_wrapper));
+    world.set_min_width(inner, Units::Pixels(0.0));
+    world.set_layout_type(inner, LayoutType::Column);
+
+    let toolbar = world.add(Some(inner));
+    world.set_min_width(toolbar, Units::Pixels(0.0));
+    world.set_layout_type(toolbar, LayoutType::Row);
+    world.set_layout_collapse(toolbar, true);
+
+    let mut tools = Vec::new();
+    for priority in 0..3 {
+        let tool = world.add(Some(toolbar));
+        world.set_width(tool, Units::Pixels(100.0));
+        world.set_collapse_priority(tool, priority);
+        tools.push(tool);
+    }
+
+    let sibling = world.add(Some(root));
+    world.set_height(sibling, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert!(world.cache.collapsed(tools[0]));
+
+    // The toolbar is two levels below the dirty node and has room for all of its tools once it grows
+    world.set_width(collapse_wrapper, Units::Pixels(400.0));
+    world.mark_dirty(collapse_wrapper);
+
+    for _ in 0..3 {
+        world.set_height(sibling, Units::Pixels(world.cache.height(sibling) + 10.0));
+        world.mark_dirty(sibling);
+
+        relayout(&mut world.cache, &world.tree, &world.store);
+    }
+
+    let nodes = [
+        grid,
+        cell,
+        wrap_row,
+        wrap_child1,
+        wrap_child2,
+        toolbar,
+        tools[0],
+        tools[1],
+        tools[2],
+        sibling,
+    ];
+
+    let relayout = nodes
+        .iter()
+        .map(|&node| {
+            (
+                world.cache.posx(node),
+                world.cache.posy(node),
+                world.cache.width(node),
+                world.cache.height(node),
+                world.cache.collapsed(node),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert!(!world.cache.collapsed(tools[0]));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    let full_layout = nodes
+        .iter()
+        .map(|&node| {
+            (
+                world.cache.posx(node),
+                world.cache.posy(node),
+                world.cache.width(node),
+                world.cache.height(node),
+                world.cache.collapsed(node),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(relayout, full_layout);
+}
@@ -0,0 +1,147 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of a reversed row which places its first child at the right
+#[test]
+fn reverse_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_reverse(row, true);
+    world.set_child_right(row, Units::Pixels(5.0));
+    world.set_col_between(row, Units::Pixels(10.0));
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(50.0));
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 495.0);
+    assert_eq!(world.cache.posx(child2), 385.0);
+    assert_eq!(world.cache.posx(child3), 275.0);
+}
+
+/// Test of a reversed column which anchors its children to the bottom
+#[test]
+fn reverse_column() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(300.0));
+    world.set_height(column, Units::Pixels(400.0));
+    world.set_layout_type(column, LayoutType::Column);
+    world.set_layout_reverse(column, true);
+
+    let child1 = world.add(Some(column));
+    world.set_width(child1, Units::Pixels(300.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(column));
+    world.set_width(child2, Units::Pixels(300.0));
+    world.set_height(child2, Units::Pixels(70.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posy(child1), 350.0);
+    assert_eq!(world.cache.posy(child2), 280.0);
+}
+
+/// Test of stretch child space on a reversed row which follows the reversed order
+#[test]
+fn reverse_row_child_space() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_reverse(row, true);
+    world.set_child_left(row, Units::Stretch(1.0));
+    world.set_child_right(row, Units::Stretch(2.0));
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(50.0));
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child3), 100.0);
+    assert_eq!(world.cache.posx(child2), 200.0);
+    assert_eq!(world.cache.posx(child1), 300.0);
+}
+
+/// Test of a reversed wrapping row which reverses the children within each line
+#[test]
+fn reverse_wrap_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(320.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+    world.set_layout_reverse(row, true);
+    world.set_col_between(row, Units::Pixels(10.0));
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(50.0));
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(50.0));
+
+    let child4 = world.add(Some(row));
+    world.set_width(child4, Units::Pixels(100.0));
+    world.set_height(child4, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 220.0);
+    assert_eq!(world.cache.posy(child1), 0.0);
+    assert_eq!(world.cache.posx(child2), 110.0);
+    assert_eq!(world.cache.posx(child3), 0.0);
+
+    assert_eq!(world.cache.posx(child4), 220.0);
+    assert_eq!(world.cache.posy(child4), 50.0);
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "rounding")]
+
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of stretch children of a row which are rounded so that they tile the row without a gap
+#[test]
+fn rounding_row_stretch() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(100.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    let child2 = world.add(Some(row));
+    let child3 = world.add(Some(row));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.width(child1), 33.0);
+
+    assert_eq!(world.cache.posx(child2), 33.0);
+    assert_eq!(world.cache.width(child2), 34.0);
+
+    assert_eq!(world.cache.posx(child3), 67.0);
+    assert_eq!(world.cache.width(child3), 33.0);
+}
+
+/// Test of stretch space between the children of a column which is rounded so that the space adds up
+#[test]
+fn rounding_column_stretch_space() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(100.0));
+    world.set_height(column, Units::Pixels(100.0));
+    world.set_layout_type(column, LayoutType::Column);
+    world.set_child_space(column, Units::Stretch(1.0));
+    world.set_row_between(column, Units::Stretch(1.0));
+
+    let child1 = world.add(Some(column));
+    world.set_height(child1, Units::Pixels(25.0));
+
+    let child2 = world.add(Some(column));
+    world.set_height(child2, Units::Pixels(25.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posy(child1), 17.0);
+    assert_eq!(world.cache.posy(child2), 58.0);
+
+    // The space below the last child takes up the rest of the column
+    assert_eq!(world.cache.bottom(child2), 17.0);
+}
+
+/// Test of stretch tracks of a grid which are rounded so that the cells meet without a seam
+#[test]
+fn rounding_grid_stretch_tracks() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let grid = world.add(Some(root));
+    world.set_width(grid, Units::Pixels(100.0));
+    world.set_height(grid, Units::Pixels(50.0));
+    world.set_layout_type(grid, LayoutType::Grid);
+    world.set_grid_rows(grid, vec![Units::Stretch(1.0)]);
+    world.set_grid_cols(grid, vec![Units::Stretch(1.0), Units::Stretch(1.0), Units::Stretch(1.0)]);
+
+    let mut cells = Vec::new();
+    for col in 0..3 {
+        let cell = world.add(Some(grid));
+        world.set_row(cell, 0, 1);
+        world.set_col(cell, col, 1);
+        cells.push(cell);
+    }
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(cells[0]), 0.0);
+    assert_eq!(world.cache.width(cells[0]), 33.0);
+
+    assert_eq!(world.cache.posx(cells[1]), 33.0);
+    assert_eq!(world.cache.width(cells[1]), 34.0);
+
+    assert_eq!(world.cache.posx(cells[2]), 67.0);
+    assert_eq!(world.cache.width(cells[2]), 33.0);
+}
@@ -0,0 +1,89 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of the edges of a node which are rounded to whole physical pixels
+#[test]
+fn scale_factor_snapping() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let child = world.add(Some(root));
+    world.set_left(child, Units::Pixels(10.3));
+    world.set_width(child, Units::Pixels(20.1));
+    world.set_height(child, Units::Pixels(50.0));
+
+    let options = LayoutOptions::default().rounding(Rounding::Pixels).scale_factor(2.0);
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.posx(child), 10.5);
+    assert_eq!(world.cache.width(child), 20.0);
+
+    assert_eq!(world.cache.physical_posx(child), 21.0);
+    assert_eq!(world.cache.physical_width(child), 40.0);
+}
+
+/// Test of stretch children of a row which tile the row in physical pixels
+#[test]
+fn scale_factor_stretch() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(50.5));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    let child2 = world.add(Some(row));
+    let child3 = world.add(Some(row));
+
+    let options = LayoutOptions::default().rounding(Rounding::Pixels).scale_factor(2.0);
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.width(child1), 17.0);
+    assert_eq!(world.cache.posx(child2), 17.0);
+    assert_eq!(world.cache.width(child2), 16.5);
+    assert_eq!(world.cache.posx(child3), 33.5);
+    assert_eq!(world.cache.width(child3), 17.0);
+
+    assert_eq!(world.cache.physical_posx(child2), 34.0);
+    assert_eq!(world.cache.physical_width(child2), 33.0);
+    assert_eq!(world.cache.physical_posx(child3), 67.0);
+    assert_eq!(world.cache.physical_width(child3), 34.0);
+}
+
+/// Test of the physical position and size of a node laid out without rounding
+#[test]
+fn scale_factor_without_rounding() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let child = world.add(Some(root));
+    world.set_left(child, Units::Pixels(10.0));
+    world.set_width(child, Units::Pixels(20.0));
+    world.set_height(child, Units::Pixels(50.0));
+
+    let options = LayoutOptions::default().rounding(Rounding::None).scale_factor(1.5);
+    layout_with_options(&mut world.cache, &world.tree, &world.store, &options);
+
+    assert_eq!(world.cache.scale_factor(), 1.5);
+    assert_eq!(world.cache.posx(child), 10.0);
+    assert_eq!(world.cache.physical_posx(child), 15.0);
+    assert_eq!(world.cache.physical_width(child), 30.0);
+
+    // The default options have a scale factor of 1
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.scale_factor(), 1.0);
+    assert_eq!(world.cache.physical_width(child), 20.0);
+}
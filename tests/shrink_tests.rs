@@ -0,0 +1,195 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of the children of a row which shrink to fit when they overflow it
+#[test]
+fn shrink_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(50.0));
+    // The row is allowed to be narrower than its children
+    world.set_min_width(row, Units::Pixels(0.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(200.0));
+    world.set_height(child1, Units::Pixels(50.0));
+    world.set_shrink(child1, 1.0);
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(200.0));
+    world.set_height(child2, Units::Pixels(50.0));
+    world.set_shrink(child2, 1.0);
+
+    // A child without a shrink factor keeps its size
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(50.0));
+    world.set_shrink(child3, 0.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.width(child1), 100.0);
+
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.width(child2), 100.0);
+
+    assert_eq!(world.cache.posx(child3), 200.0);
+    assert_eq!(world.cache.width(child3), 100.0);
+}
+
+/// Test of shrinking children which are limited by their min width
+#[test]
+fn shrink_row_min_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_min_width(row, Units::Pixels(0.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(200.0));
+    world.set_height(child1, Units::Pixels(50.0));
+    world.set_shrink(child1, 1.0);
+    world.set_min_width(child1, Units::Pixels(180.0));
+
+    // The overflow which the first child cannot give back is taken from the second
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(200.0));
+    world.set_height(child2, Units::Pixels(50.0));
+    world.set_shrink(child2, 1.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.width(child1), 180.0);
+
+    assert_eq!(world.cache.posx(child2), 180.0);
+    assert_eq!(world.cache.width(child2), 120.0);
+}
+
+/// Test of the children of a column which shrink in proportion to their shrink factors
+#[test]
+fn shrink_column_factors() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(100.0));
+    world.set_height(column, Units::Pixels(200.0));
+    world.set_min_height(column, Units::Pixels(0.0));
+    world.set_layout_type(column, LayoutType::Column);
+    world.set_row_between(column, Units::Pixels(20.0));
+
+    let child1 = world.add(Some(column));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(100.0));
+    world.set_shrink(child1, 1.0);
+
+    let child2 = world.add(Some(column));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(160.0));
+    world.set_shrink(child2, 3.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posy(child1), 0.0);
+    assert_eq!(world.cache.height(child1), 80.0);
+
+    // The shrink factors do not change the width of the children
+    assert_eq!(world.cache.width(child1), 100.0);
+
+    assert_eq!(world.cache.posy(child2), 100.0);
+    assert_eq!(world.cache.height(child2), 100.0);
+}
+
+/// Test of the children of a row with the default min width, which shrink to fit rather than grow the row
+#[test]
+fn shrink_row_default_min_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(150.0));
+    world.set_height(child1, Units::Pixels(50.0));
+    world.set_shrink(child1, 1.0);
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(150.0));
+    world.set_height(child2, Units::Pixels(50.0));
+    world.set_shrink(child2, 1.0);
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(150.0));
+    world.set_height(child3, Units::Pixels(50.0));
+    world.set_shrink(child3, 1.0);
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(row), 300.0);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.width(child1), 100.0);
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.width(child2), 100.0);
+    assert_eq!(world.cache.posx(child3), 200.0);
+    assert_eq!(world.cache.width(child3), 100.0);
+}
+
+/// Test of a row with the default min width, which grows only as far as the min widths of its shrinking children
+#[test]
+fn shrink_row_default_min_width_children_min() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(50.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(250.0));
+    world.set_height(child1, Units::Pixels(50.0));
+    world.set_min_width(child1, Units::Pixels(200.0));
+    world.set_shrink(child1, 1.0);
+
+    // A child without a shrink factor takes up its full width
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(150.0));
+    world.set_height(child2, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(row), 350.0);
+
+    assert_eq!(world.cache.width(child1), 200.0);
+    assert_eq!(world.cache.posx(child2), 200.0);
+    assert_eq!(world.cache.width(child2), 150.0);
+}
@@ -0,0 +1,120 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of the space given up by a stretch child clamped by its max which is shared by its siblings
+#[test]
+fn stretch_size_redistributed() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    let child2 = world.add(Some(row));
+    let child3 = world.add(Some(row));
+    world.set_max_width(child3, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 250.0);
+    assert_eq!(world.cache.posx(child2), 250.0);
+    assert_eq!(world.cache.width(child2), 250.0);
+    assert_eq!(world.cache.posx(child3), 500.0);
+    assert_eq!(world.cache.width(child3), 100.0);
+}
+
+/// Test of stretch space which is shared again when some of it is clamped by its max
+#[test]
+fn stretch_space_redistributed() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_left(child1, Units::Stretch(1.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_left(child2, Units::Stretch(1.0));
+    world.set_right(child2, Units::Stretch(1.0));
+    world.store.max_right.insert(child2, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 175.0);
+    assert_eq!(world.cache.posx(child2), 450.0);
+    assert_eq!(world.cache.right(child2), 50.0);
+}
+
+/// Test of stretch space and size across the layout direction which is shared again after clamping
+#[test]
+fn stretch_cross_axis_redistributed() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(400.0));
+    world.set_height(column, Units::Pixels(200.0));
+
+    let child = world.add(Some(column));
+    world.set_left(child, Units::Stretch(1.0));
+    world.set_right(child, Units::Stretch(1.0));
+    world.store.max_right.insert(child, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child), 175.0);
+    assert_eq!(world.cache.width(child), 175.0);
+}
+
+/// Test of the space given up by a stretch child clamped by its max in a stretch row with the default min width,
+/// which is shared by the siblings which are not clamped by their min
+#[test]
+fn stretch_size_redistributed_default_min_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+    world.set_layout_type(root, LayoutType::Column);
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Stretch(1.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_min_width(child1, Units::Pixels(500.0));
+
+    let child2 = world.add(Some(row));
+
+    let child3 = world.add(Some(row));
+    world.set_max_width(child3, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(row), 1000.0);
+
+    assert_eq!(world.cache.width(child1), 500.0);
+    assert_eq!(world.cache.posx(child2), 500.0);
+    assert_eq!(world.cache.width(child2), 400.0);
+    assert_eq!(world.cache.posx(child3), 900.0);
+    assert_eq!(world.cache.width(child3), 100.0);
+}
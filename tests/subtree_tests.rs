@@ -0,0 +1,120 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of a stretch subtree which fills the available size at the position, without disturbing the rest of the tree
+#[test]
+fn subtree_stretch() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let child = world.add(Some(root));
+    world.set_width(child, Units::Pixels(100.0));
+    world.set_height(child, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    let popup = world.add(Some(root));
+    world.set_position_type(popup, PositionType::SelfDirected);
+    world.set_child_space(popup, Units::Pixels(10.0));
+
+    let item = world.add(Some(popup));
+
+    layout_subtree(
+        &mut world.cache,
+        &world.tree,
+        &world.store,
+        popup,
+        (200.0, 100.0),
+        (300.0, 200.0),
+    );
+
+    assert_eq!(world.cache.posx(popup), 200.0);
+    assert_eq!(world.cache.posy(popup), 100.0);
+    assert_eq!(world.cache.width(popup), 300.0);
+    assert_eq!(world.cache.height(popup), 200.0);
+
+    assert_eq!(world.cache.posx(item), 210.0);
+    assert_eq!(world.cache.posy(item), 110.0);
+    assert_eq!(world.cache.width(item), 280.0);
+    assert_eq!(world.cache.height(item), 180.0);
+
+    assert_eq!(world.cache.posx(child), 0.0);
+    assert_eq!(world.cache.posy(child), 0.0);
+    assert_eq!(world.cache.width(child), 100.0);
+    assert_eq!(world.cache.height(child), 100.0);
+}
+
+/// Test of an auto sized subtree which takes up the size of its children rather than the available size
+#[test]
+fn subtree_auto() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Auto);
+    world.set_height(root, Units::Auto);
+    world.set_layout_type(root, LayoutType::Row);
+
+    let child1 = world.add(Some(root));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    let child2 = world.add(Some(root));
+    world.set_width(child2, Units::Pixels(60.0));
+    world.set_height(child2, Units::Pixels(80.0));
+
+    layout_subtree(
+        &mut world.cache,
+        &world.tree,
+        &world.store,
+        root,
+        (20.0, 30.0),
+        (1000.0, 600.0),
+    );
+
+    assert_eq!(world.cache.posx(root), 20.0);
+    assert_eq!(world.cache.posy(root), 30.0);
+    assert_eq!(world.cache.width(root), 160.0);
+    assert_eq!(world.cache.height(root), 80.0);
+
+    assert_eq!(world.cache.posx(child1), 20.0);
+    assert_eq!(world.cache.posx(child2), 120.0);
+    assert_eq!(world.cache.posy(child2), 30.0);
+}
+
+/// Test of a percentage sized subtree, whose parent keeps the sums of its children from the previous layout
+#[test]
+fn subtree_percentage() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let node = world.add(Some(root));
+    world.set_width(node, Units::Pixels(100.0));
+    world.set_height(node, Units::Pixels(100.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    let width_sum = world.cache.child_width_sum(root);
+    let height_sum = world.cache.child_height_sum(root);
+
+    world.set_width(node, Units::Percentage(50.0));
+    world.set_height(node, Units::Percentage(25.0));
+
+    let child = world.add(Some(node));
+
+    layout_subtree(&mut world.cache, &world.tree, &world.store, node, (0.0, 0.0), (400.0, 200.0));
+
+    assert_eq!(world.cache.width(node), 200.0);
+    assert_eq!(world.cache.height(node), 50.0);
+
+    assert_eq!(world.cache.width(child), 200.0);
+    assert_eq!(world.cache.height(child), 50.0);
+
+    assert_eq!(world.cache.child_width_sum(root), width_sum);
+    assert_eq!(world.cache.child_height_sum(root), height_sum);
+}
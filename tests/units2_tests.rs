@@ -0,0 +1,125 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of stretch sizes with an inline min and max
+#[test]
+fn units2_stretch_min_max() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units2::stretch(1.0).min(100.0).max(200.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units2::stretch(1.0));
+    world.set_height(child2, Units2::stretch(1.0).max(120.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 200.0);
+    assert_eq!(world.cache.height(child1), 200.0);
+
+    assert_eq!(world.cache.posx(child2), 200.0);
+    assert_eq!(world.cache.width(child2), 400.0);
+    assert_eq!(world.cache.height(child2), 120.0);
+}
+
+/// Test of pixel and percentage space and size with an inline min and max
+#[test]
+fn units2_pixels_percentage() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(600.0));
+    world.set_height(column, Units::Pixels(200.0));
+
+    let child = world.add(Some(column));
+    world.set_left(child, Units2::percentage(10.0).max(40.0));
+    world.set_top(child, Units2::pixels(5.0).min(10.0));
+    world.set_width(child, Units2::percentage(50.0).min(350.0));
+    world.set_height(child, Units2::percentage(10.0).min(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child), 40.0);
+    assert_eq!(world.cache.posy(child), 10.0);
+    assert_eq!(world.cache.width(child), 350.0);
+    assert_eq!(world.cache.height(child), 50.0);
+}
+
+/// Test of inline bounds which apply together with the min and max of the node
+#[test]
+fn units2_with_min_max_size() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Auto);
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+
+    // The larger of the two minimums applies
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units2::pixels(50.0).min(100.0));
+    world.set_min_width(child1, Units::Pixels(150.0));
+    world.set_height(child1, Units::Pixels(50.0));
+
+    // The smaller of the two maximums applies
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units2::pixels(300.0).max(250.0));
+    world.set_max_width(child2, Units::Pixels(280.0));
+    world.set_height(child2, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 150.0);
+    assert_eq!(world.cache.width(child2), 250.0);
+    assert_eq!(world.cache.width(row), 400.0);
+}
+
+/// Test of an inline min above the inline max, where the max wins whichever is set first
+#[test]
+fn units2_min_above_max() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(600.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units2::stretch(1.0).min(300.0).max(100.0));
+    world.set_height(child1, Units2::pixels(50.0).min(80.0).max(60.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units2::percentage(50.0).max(100.0).min(300.0));
+    world.set_height(child2, Units2::Calc(Calc::new(10.0, 10.0)).max(20.0).min(40.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(child1), 100.0);
+    assert_eq!(world.cache.height(child1), 60.0);
+
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.width(child2), 100.0);
+    assert_eq!(world.cache.height(child2), 20.0);
+}
@@ -0,0 +1,311 @@
+use morphorm::*;
+use morphorm_ecs::*;
+
+/// Test of the children of a wrapping row which move to a new line when they do not fit
+#[test]
+fn wrap_row() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(320.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+    world.set_col_between(row, Units::Pixels(10.0));
+    world.set_row_between(row, Units::Pixels(20.0));
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(30.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(100.0));
+    world.set_height(child2, Units::Pixels(60.0));
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(40.0));
+
+    let child4 = world.add(Some(row));
+    world.set_width(child4, Units::Pixels(150.0));
+    world.set_height(child4, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.posy(child1), 0.0);
+    assert_eq!(world.cache.posx(child2), 110.0);
+    assert_eq!(world.cache.posy(child2), 0.0);
+    assert_eq!(world.cache.posx(child3), 220.0);
+    assert_eq!(world.cache.posy(child3), 0.0);
+
+    // The first line is as tall as its tallest child
+    assert_eq!(world.cache.posx(child4), 0.0);
+    assert_eq!(world.cache.posy(child4), 80.0);
+    assert_eq!(world.cache.width(child4), 150.0);
+    assert_eq!(world.cache.height(child4), 50.0);
+}
+
+/// Test of stretch space and size within the lines of a wrapping row
+#[test]
+fn wrap_row_stretch() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(300.0));
+    world.set_height(row, Units::Pixels(200.0));
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(40.0));
+
+    // A stretch child takes up its minimum width when the lines are split
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Stretch(1.0));
+    world.set_min_width(child2, Units::Pixels(150.0));
+    world.set_height(child2, Units::Stretch(1.0));
+
+    let child3 = world.add(Some(row));
+    world.set_width(child3, Units::Pixels(100.0));
+    world.set_height(child3, Units::Pixels(30.0));
+    world.set_left(child3, Units::Stretch(1.0));
+    world.set_right(child3, Units::Stretch(1.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 0.0);
+    assert_eq!(world.cache.posy(child1), 0.0);
+
+    assert_eq!(world.cache.posx(child2), 100.0);
+    assert_eq!(world.cache.posy(child2), 0.0);
+    assert_eq!(world.cache.width(child2), 200.0);
+    assert_eq!(world.cache.height(child2), 40.0);
+
+    assert_eq!(world.cache.posx(child3), 100.0);
+    assert_eq!(world.cache.posy(child3), 40.0);
+}
+
+/// Test of a wrapping column with child space around the lines
+#[test]
+fn wrap_column() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let column = world.add(Some(root));
+    world.set_width(column, Units::Pixels(300.0));
+    world.set_height(column, Units::Pixels(200.0));
+    world.set_layout_type(column, LayoutType::Column);
+    world.set_layout_wrap(column, true);
+    world.set_child_space(column, Units::Pixels(10.0));
+    world.set_col_between(column, Units::Pixels(5.0));
+
+    let child1 = world.add(Some(column));
+    world.set_width(child1, Units::Pixels(50.0));
+    world.set_height(child1, Units::Pixels(80.0));
+
+    let child2 = world.add(Some(column));
+    world.set_width(child2, Units::Pixels(40.0));
+    world.set_height(child2, Units::Pixels(80.0));
+
+    let child3 = world.add(Some(column));
+    world.set_width(child3, Units::Pixels(60.0));
+    world.set_height(child3, Units::Pixels(80.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.posx(child1), 10.0);
+    assert_eq!(world.cache.posy(child1), 10.0);
+    assert_eq!(world.cache.posx(child2), 10.0);
+    assert_eq!(world.cache.posy(child2), 90.0);
+    assert_eq!(world.cache.posx(child3), 65.0);
+    assert_eq!(world.cache.posy(child3), 10.0);
+}
+
+/// Test of auto height on a wrapping row with a width in pixels
+#[test]
+fn wrap_row_auto_height() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let row = world.add(Some(root));
+    world.set_width(row, Units::Pixels(320.0));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+    world.set_col_between(row, Units::Pixels(10.0));
+    world.set_row_between(row, Units::Pixels(20.0));
+
+    for _ in 0..4 {
+        let child = world.add(Some(row));
+        world.set_width(child, Units::Pixels(100.0));
+        world.set_height(child, Units::Pixels(50.0));
+    }
+
+    // An auto width is limited by the maximum width
+    let auto_row = world.add(Some(root));
+    world.set_width(auto_row, Units::Auto);
+    world.set_height(auto_row, Units::Auto);
+    world.set_max_width(auto_row, Units::Pixels(250.0));
+    world.set_layout_type(auto_row, LayoutType::Row);
+    world.set_layout_wrap(auto_row, true);
+
+    for _ in 0..5 {
+        let child = world.add(Some(auto_row));
+        world.set_width(child, Units::Pixels(100.0));
+        world.set_height(child, Units::Pixels(50.0));
+    }
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(row), 320.0);
+    assert_eq!(world.cache.height(row), 120.0);
+
+    assert_eq!(world.cache.width(auto_row), 200.0);
+    assert_eq!(world.cache.height(auto_row), 150.0);
+}
+
+/// Test of a wrapping row with a stretch width, which wraps within the width given by its parent
+#[test]
+fn wrap_row_stretch_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let parent = world.add(Some(root));
+    world.set_width(parent, Units::Pixels(300.0));
+    world.set_height(parent, Units::Pixels(400.0));
+    world.set_min_width(parent, Units::Pixels(0.0));
+    world.set_layout_type(parent, LayoutType::Column);
+
+    let row = world.add(Some(parent));
+    world.set_width(row, Units::Stretch(1.0));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+
+    let mut children = Vec::new();
+    for _ in 0..5 {
+        let child = world.add(Some(row));
+        world.set_width(child, Units::Pixels(100.0));
+        world.set_height(child, Units::Pixels(30.0));
+        children.push(child);
+    }
+
+    let sibling = world.add(Some(parent));
+    world.set_height(sibling, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(parent), 300.0);
+    assert_eq!(world.cache.width(row), 300.0);
+    assert_eq!(world.cache.height(row), 60.0);
+
+    assert_eq!(world.cache.posx(children[2]), 200.0);
+    assert_eq!(world.cache.posy(children[2]), 0.0);
+    assert_eq!(world.cache.posx(children[3]), 0.0);
+    assert_eq!(world.cache.posy(children[3]), 30.0);
+
+    // The sibling is placed below all of the lines
+    assert_eq!(world.cache.posy(sibling), 60.0);
+}
+
+/// Test of a wrapping row with a percentage width, which wraps within its share of the width of its parent
+#[test]
+fn wrap_row_percentage_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let parent = world.add(Some(root));
+    world.set_width(parent, Units::Pixels(400.0));
+    world.set_height(parent, Units::Pixels(400.0));
+    world.set_min_width(parent, Units::Pixels(0.0));
+    world.set_layout_type(parent, LayoutType::Column);
+
+    let row = world.add(Some(parent));
+    world.set_width(row, Units::Percentage(50.0));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+
+    let mut children = Vec::new();
+    for _ in 0..5 {
+        let child = world.add(Some(row));
+        world.set_width(child, Units::Pixels(100.0));
+        world.set_height(child, Units::Pixels(30.0));
+        children.push(child);
+    }
+
+    let sibling = world.add(Some(parent));
+    world.set_height(sibling, Units::Pixels(50.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    assert_eq!(world.cache.width(row), 200.0);
+    assert_eq!(world.cache.height(row), 90.0);
+
+    assert_eq!(world.cache.posx(children[1]), 100.0);
+    assert_eq!(world.cache.posx(children[2]), 0.0);
+    assert_eq!(world.cache.posy(children[2]), 30.0);
+    assert_eq!(world.cache.posy(children[4]), 60.0);
+
+    assert_eq!(world.cache.posy(sibling), 90.0);
+}
+
+/// Test of a wrapping row with a stretch width, which can shrink to its widest child
+#[test]
+fn wrap_row_stretch_min_width() {
+    let mut world = World::default();
+
+    let root = world.add(None);
+    world.set_width(root, Units::Pixels(1000.0));
+    world.set_height(root, Units::Pixels(600.0));
+
+    let parent = world.add(Some(root));
+    world.set_width(parent, Units::Auto);
+    world.set_height(parent, Units::Pixels(400.0));
+    world.set_max_width(parent, Units::Pixels(1000.0));
+    world.set_layout_type(parent, LayoutType::Column);
+
+    let row = world.add(Some(parent));
+    world.set_width(row, Units::Stretch(1.0));
+    world.set_height(row, Units::Auto);
+    world.set_layout_type(row, LayoutType::Row);
+    world.set_layout_wrap(row, true);
+
+    let child1 = world.add(Some(row));
+    world.set_width(child1, Units::Pixels(100.0));
+    world.set_height(child1, Units::Pixels(30.0));
+
+    let child2 = world.add(Some(row));
+    world.set_width(child2, Units::Pixels(150.0));
+    world.set_height(child2, Units::Pixels(30.0));
+
+    layout(&mut world.cache, &world.tree, &world.store);
+
+    // The auto width of the parent fits the widest child rather than all of the children on one line
+    assert_eq!(world.cache.width(parent), 150.0);
+    assert_eq!(world.cache.width(row), 150.0);
+    assert_eq!(world.cache.height(row), 60.0);
+    assert_eq!(world.cache.posy(child2), 30.0);
+}